@@ -1,33 +1,65 @@
-use criterion::{Criterion, criterion_group, criterion_main};
+use std::{borrow::Cow, hint::black_box};
+
+use aoc::{Solution, Variant};
+use criterion::{
+    BenchmarkGroup, Criterion, Throughput, criterion_group, criterion_main, measurement::WallTime,
+};
 use pprof::criterion::{Output, PProfProfiler};
 
 const PPROF_SAMPLING_FREQ_HZ: i32 = 997;
 
+/// Puzzle input for `day`, or `None` (with a note on stderr) if it isn't available on this machine.
+fn input(day: u8) -> Option<Cow<'static, str>> {
+    match aoc::input::puzzle_input(day) {
+        Ok(input) => Some(input),
+        Err(err) => {
+            eprintln!("Skipping day {:02} benchmarks: {}", day, err);
+            None
+        }
+    }
+}
+
+/// Benchmarks each variant of a part on the already parsed input, as `dayNN/partN@name (solve)`.
+fn bench_variants<P, T>(
+    group: &mut BenchmarkGroup<WallTime>,
+    part: &str,
+    parsed: &P,
+    variants: Vec<Variant<P, T>>,
+) {
+    for variant in variants {
+        group.bench_function(format!("{}@{} (solve)", part, variant.name), |b| {
+            b.iter(|| (variant.solve)(black_box(parsed)))
+        });
+    }
+}
+
+/// Benchmarks parsing `day`'s input and solving each part (and its variants) from the parsed input,
+/// in a group per day. Throughput is reported against the size of the input.
+fn bench_day<S: Solution>(c: &mut Criterion, day: u8) {
+    let Some(input) = input(day) else { return };
+    let parsed = S::parse(&input).expect("parsing input");
+    let mut group = c.benchmark_group(format!("day{:02}", day));
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&input))));
+    group.bench_function("part1 (solve)", |b| b.iter(|| S::part1(black_box(&parsed))));
+    bench_variants(&mut group, "part1", &parsed, S::part1_variants());
+    group.bench_function("part2 (solve)", |b| b.iter(|| S::part2(black_box(&parsed))));
+    bench_variants(&mut group, "part2", &parsed, S::part2_variants());
+    group.finish();
+}
+
 macro_rules! bench_days {
     ($($day_num:literal => $day_mod:ident),* $(,)?) => {
         $(
-            mod $day_mod {
-                use super::*;
-                use aoc::$day_mod;
-
-                pub fn part1(c: &mut Criterion) {
-                    c.bench_function(concat!(stringify!($day_mod), " part1"), |b| {
-                        b.iter(|| $day_mod::part1($day_mod::INPUT))
-                    });
-                }
-
-                pub fn part2(c: &mut Criterion) {
-                    c.bench_function(concat!(stringify!($day_mod), " part2"), |b| {
-                        b.iter(|| $day_mod::part2($day_mod::INPUT))
-                    });
-                }
+            fn $day_mod(c: &mut Criterion) {
+                bench_day::<aoc::$day_mod::Day>(c, $day_num);
             }
         )*
 
         criterion_group! {
             name = benches;
             config = Criterion::default().with_profiler(PProfProfiler::new(PPROF_SAMPLING_FREQ_HZ, Output::Flamegraph(None)));
-            targets = $($day_mod::part1, $day_mod::part2),*
+            targets = $($day_mod),*
         }
         criterion_main!(benches);
     };
@@ -0,0 +1,51 @@
+//! Discovers the implemented days from the `src/dayNN/` directories that have a `mod.rs`, and
+//! generates their module declarations and the `all_days!` macro from them.
+
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let src = Path::new(&manifest_dir).join("src");
+    println!("cargo::rerun-if-changed={}", src.display());
+
+    let mut days: Vec<(u8, String)> = fs::read_dir(&src)
+        .unwrap()
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name().into_string().ok()?;
+            let day = name.strip_prefix("day")?;
+            if day.len() != 2 || !entry.path().join("mod.rs").is_file() {
+                return None;
+            }
+            Some((day.parse().ok()?, name))
+        })
+        .collect();
+    days.sort();
+
+    let mut modules = String::new();
+    let mut entries = String::new();
+    for (day, name) in &days {
+        let path = src.join(name).join("mod.rs");
+        modules += &format!(
+            "#[path = {:?}]\npub mod {};\n",
+            path.display().to_string(),
+            name
+        );
+        entries += &format!("            {} => {},\n", day, name);
+    }
+    let all_days = format!(
+        "#[macro_export]
+macro_rules! all_days {{
+    ($macro_name:path) => {{
+        $macro_name! {{
+{}        }}
+    }};
+}}
+",
+        entries
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("day_modules.rs"), modules).unwrap();
+    fs::write(Path::new(&out_dir).join("all_days.rs"), all_days).unwrap();
+}
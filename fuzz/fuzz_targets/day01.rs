@@ -0,0 +1,13 @@
+#![no_main]
+
+use aoc::{Solution, day01::Day};
+use libfuzzer_sys::fuzz_target;
+
+// Every input must give an answer or an error, never a panic
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = Day::validate(input);
+        let _ = Day::solve_part1(input);
+        let _ = Day::solve_part2(input);
+    }
+});
@@ -0,0 +1,183 @@
+use std::{
+    convert::Infallible,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A puzzle answer. Days return whatever integer type suits them, so equality compares integers
+/// by value regardless of which variant holds them. Text that spells an integer counts as that
+/// integer, since it's saved as one.
+///
+/// Answers serialize as integers when they fit in an `i64` (the limit for TOML) and as strings
+/// otherwise, so manifests and JSON output look the same for every day.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    /// Integers too big for `i64` or `u64`.
+    Big(i128),
+    Text(String),
+}
+
+impl Answer {
+    /// The answer as an integer, if it is one or is text that parses as one.
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Answer::Signed(n) => Some(*n as i128),
+            Answer::Unsigned(n) => Some(*n as i128),
+            Answer::Big(n) => Some(*n),
+            Answer::Text(s) => s.parse().ok(),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.as_i128(), other.as_i128()) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => self.to_string() == other.to_string(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Signed(n) => write!(f, "{}", n),
+            Answer::Unsigned(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($variant:ident, $as:ty, $($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(n: $int) -> Self {
+                    Answer::$variant(n as $as)
+                }
+            }
+        )*
+    };
+}
+
+from_int!(Signed, i64, i8, i16, i32, i64, isize);
+from_int!(Unsigned, u64, u8, u16, u32, u64, usize);
+from_int!(Big, i128, i128);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    /// Parses integers into the narrowest variant that holds them. Anything else is text.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(if let Ok(n) = s.parse() {
+            Answer::Signed(n)
+        } else if let Ok(n) = s.parse() {
+            Answer::Unsigned(n)
+        } else if let Ok(n) = s.parse() {
+            Answer::Big(n)
+        } else {
+            Answer::Text(s.to_string())
+        })
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_i128().and_then(|n| i64::try_from(n).ok()) {
+            Some(n) => serializer.serialize_i64(n),
+            None => serializer.serialize_str(&self.to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Integer(i64),
+            Text(String),
+        }
+
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Integer(n) => Answer::Signed(n),
+            Raw::Text(s) => s.parse().unwrap_or(Answer::Text(s)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_eq_ignores_width() {
+        assert_eq!(Answer::from(3i32), Answer::from(3usize));
+        assert_eq!(Answer::from(-3i64), Answer::Big(-3));
+        assert_ne!(Answer::from(3u64), Answer::from(4u64));
+        assert_eq!(Answer::from(3u64), Answer::from("3"));
+        assert_ne!(Answer::from(3u64), Answer::from("03x"));
+        assert_eq!(Answer::from("abc"), Answer::Text("abc".to_string()));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("-5".parse::<Answer>().unwrap(), Answer::Signed(-5));
+        assert!(matches!(
+            "18446744073709551615".parse::<Answer>().unwrap(),
+            Answer::Unsigned(u64::MAX)
+        ));
+        assert!(matches!(
+            "98765432109876543210".parse::<Answer>().unwrap(),
+            Answer::Big(98765432109876543210)
+        ));
+        assert!(matches!(
+            "abc".parse::<Answer>().unwrap(),
+            Answer::Text(s) if s == "abc"
+        ));
+    }
+
+    #[test]
+    fn test_serde() {
+        let answers = vec![
+            Answer::from(3usize),
+            Answer::from(u64::MAX),
+            Answer::from("abc"),
+        ];
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(json, r#"[3,"18446744073709551615","abc"]"#);
+        let parsed: Vec<Answer> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, answers);
+        assert!(matches!(parsed[1], Answer::Unsigned(u64::MAX)));
+    }
+
+    #[test]
+    fn test_numeric_text_round_trip() {
+        let answer = Answer::from(String::from("123"));
+        let json = serde_json::to_string(&answer).unwrap();
+        assert_eq!(json, "123");
+        let parsed: Answer = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, answer);
+    }
+}
@@ -0,0 +1,222 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+};
+
+use aoc::Answer;
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    manifest::{day_key, load_toml, save_toml},
+    rejected::{Rejected, Rejection},
+};
+
+/// Default location of the answers manifest, relative to the crate root.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Comment written at the top of the manifest whenever it's saved.
+const HEADER: &str = "\
+# Known answers for the real puzzle inputs (src/dayNN/input/input.txt).
+#
+# `cargo run` checks every answer it computes against this file and exits with an error if one
+# doesn't match. Answers too big for a TOML integer can be written as strings. `cargo run -- submit`
+# records answers here automatically once the site accepts them.
+";
+
+pub fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(ANSWERS_FILE)
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct DayAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<Answer>,
+}
+
+/// Known-correct answers for the real puzzle inputs, keyed by day (`day01`) and part.
+///
+/// ```toml
+/// [day01]
+/// part1 = 1234
+/// part2 = "98765432109876543210"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, DayAnswers>);
+
+/// Outcome of comparing a computed answer against the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    Correct,
+    Incorrect {
+        expected: Answer,
+    },
+    /// No known answer yet, but the site already rejected this one.
+    Rejected(Rejection),
+    Unknown,
+}
+
+impl Display for Verification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verification::Correct => write!(f, "✔"),
+            Verification::Incorrect { expected } => write!(f, "✘ (expected {})", expected),
+            Verification::Rejected(rejection) => write!(f, "✘ ({})", rejection),
+            Verification::Unknown => Ok(()),
+        }
+    }
+}
+
+impl Verification {
+    /// Short machine-readable name for the outcome.
+    pub fn status(&self) -> &'static str {
+        match self {
+            Verification::Correct => "correct",
+            Verification::Incorrect { .. } => "incorrect",
+            Verification::Rejected(_) => "rejected",
+            Verification::Unknown => "unknown",
+        }
+    }
+}
+
+impl Answers {
+    /// Reads the manifest at `path`. A missing manifest is treated as empty.
+    pub fn load(path: &Path) -> Result<Self> {
+        load_toml(path)
+    }
+
+    /// Writes the manifest to `path`, replacing any comments other than the standard header.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        save_toml(path, Some(HEADER), self)
+    }
+
+    /// Records `answer` as the known answer for `day` and `part`.
+    pub fn record(&mut self, day: u8, part: u8, answer: Answer) {
+        let answers = self.0.entry(day_key(day)).or_default();
+        match part {
+            1 => answers.part1 = Some(answer),
+            2 => answers.part2 = Some(answer),
+            _ => {}
+        }
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        let answers = self.0.get(&day_key(day))?;
+        match part {
+            1 => answers.part1.as_ref(),
+            2 => answers.part2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Compares `answer` against the known answer for `day` and `part`, if there is one.
+    pub fn verify(&self, day: u8, part: u8, answer: &Answer) -> Verification {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Verification::Correct,
+            Some(expected) => Verification::Incorrect {
+                expected: expected.clone(),
+            },
+            None => Verification::Unknown,
+        }
+    }
+
+    /// Like [`Answers::verify`], but when there's no known answer also checks whether `rejected`
+    /// already rules `answer` out.
+    pub fn verify_with_rejected(
+        &self,
+        rejected: &Rejected,
+        day: u8,
+        part: u8,
+        answer: &Answer,
+    ) -> Verification {
+        match self.verify(day, part, answer) {
+            Verification::Unknown => rejected
+                .check(day, part, answer)
+                .map_or(Verification::Unknown, Verification::Rejected),
+            verification => verification,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use test_log::test;
+
+    const TEST_MANIFEST: &str = r#"
+[day01]
+part1 = 3
+part2 = "6"
+
+[day02]
+part1 = 1227775554
+"#;
+
+    #[test]
+    fn test_verify() {
+        let answers: Answers = toml::from_str(TEST_MANIFEST).unwrap();
+        assert_eq!(
+            answers.verify(1, 1, &Answer::from(3)),
+            Verification::Correct
+        );
+        assert_eq!(
+            answers.verify(1, 2, &Answer::from(6)),
+            Verification::Correct
+        );
+        assert_eq!(
+            answers.verify(1, 1, &Answer::from(4)),
+            Verification::Incorrect {
+                expected: Answer::Signed(3)
+            }
+        );
+        assert_eq!(
+            answers.verify(2, 2, &Answer::from(0)),
+            Verification::Unknown
+        );
+        assert_eq!(
+            answers.verify(3, 1, &Answer::from(0)),
+            Verification::Unknown
+        );
+    }
+
+    #[test]
+    fn test_record_and_save() {
+        let mut answers: Answers = toml::from_str(TEST_MANIFEST).unwrap();
+        answers.record(2, 2, Answer::from(4174379265u64));
+        answers.record(3, 1, Answer::from("abc"));
+        answers.record(3, 2, Answer::from(String::from("123")));
+        let path = temp_dir("answers").join("answers.toml");
+        answers.save(&path).unwrap();
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert!(saved.starts_with(HEADER));
+        assert!(saved.contains("[day02]\npart1 = 1227775554\npart2 = 4174379265\n"));
+        assert!(saved.contains("[day03]\npart1 = \"abc\"\npart2 = 123\n"));
+        let reloaded = Answers::load(&path).unwrap();
+        assert_eq!(
+            reloaded.verify(2, 2, &Answer::from(4174379265u64)),
+            Verification::Correct
+        );
+        assert_eq!(
+            reloaded.verify(3, 1, &Answer::from("abc")),
+            Verification::Correct
+        );
+        assert_eq!(
+            reloaded.verify(3, 2, &Answer::from(String::from("123"))),
+            Verification::Correct
+        );
+    }
+
+    #[test]
+    fn test_load_missing_manifest() {
+        let answers = Answers::load(Path::new("does-not-exist.toml")).unwrap();
+        assert_eq!(
+            answers.verify(1, 1, &Answer::from(3)),
+            Verification::Unknown
+        );
+    }
+}
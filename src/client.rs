@@ -0,0 +1,252 @@
+use std::{
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail, eyre},
+};
+use tracing::{debug, info};
+use ureq::{Agent, http::StatusCode};
+
+pub const YEAR: u16 = 2025;
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+/// Identifies this tool to the site, as the automation guidelines ask.
+pub const USER_AGENT: &str = "github.com/thallada/advent-of-code-2025 by thallada";
+/// Environment variable holding the session cookie value.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// Environment variable overriding the site's base URL, e.g. to point at a local mock server.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+/// Minimum time between two requests to the site.
+pub const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Reads the session token from `$AOC_SESSION`, falling back to `~/.config/aoc/session`.
+pub fn session_token() -> Result<String> {
+    if let Ok(token) = std::env::var(SESSION_ENV) {
+        return Ok(token.trim().to_string());
+    }
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .ok_or_else(|| eyre!("could not determine config directory"))?;
+    let path = config_dir.join("aoc").join("session");
+    std::fs::read_to_string(&path)
+        .map(|token| token.trim().to_string())
+        .map_err(|_| {
+            eyre!(
+                "No session token found. Set ${} or save the session cookie from adventofcode.com to {}.",
+                SESSION_ENV,
+                path.display()
+            )
+        })
+}
+
+/// Base URL of the site, from `$AOC_BASE_URL` or the real site by default.
+pub fn base_url() -> String {
+    std::env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string())
+}
+
+/// Where the time of the last request is recorded, so throttling holds across runs.
+fn default_throttle_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("aoc")
+        .join("last-request")
+}
+
+/// A throttled client for the Advent of Code site.
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    session: String,
+    throttle_path: PathBuf,
+    min_interval: Duration,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        let agent = Agent::config_builder()
+            .user_agent(USER_AGENT)
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+        Client {
+            agent,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+            throttle_path: default_throttle_path(),
+            min_interval: MIN_REQUEST_INTERVAL,
+        }
+    }
+
+    /// A client for the site at [`base_url`] using the [`session_token`].
+    pub fn from_env() -> Result<Self> {
+        Ok(Client::new(base_url(), session_token()?))
+    }
+
+    /// Overrides where the last request time is recorded and how long to wait between requests.
+    #[cfg(test)]
+    pub fn with_throttle(mut self, path: PathBuf, min_interval: Duration) -> Self {
+        self.throttle_path = path;
+        self.min_interval = min_interval;
+        self
+    }
+
+    fn day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}", self.base_url, YEAR, day)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Sleeps until at least `min_interval` has passed since the last recorded request, then
+    /// records a new one.
+    fn throttle(&self) -> Result<()> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        if let Ok(last) = std::fs::read_to_string(&self.throttle_path)
+            && let Ok(last) = last.trim().parse::<u64>()
+        {
+            let next = Duration::from_millis(last) + self.min_interval;
+            if next > now {
+                let wait = next - now;
+                info!("Waiting {:.1?} before the next request", wait);
+                thread::sleep(wait);
+            }
+        }
+        if let Some(parent) = self.throttle_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        std::fs::write(&self.throttle_path, now.as_millis().to_string())
+            .wrap_err_with(|| format!("writing {}", self.throttle_path.display()))
+    }
+
+    /// Downloads the puzzle input for `day`.
+    pub fn input(&self, day: u8) -> Result<String> {
+        self.throttle()?;
+        let url = format!("{}/input", self.day_url(day));
+        debug!(url, "fetching input");
+        let mut response = self
+            .agent
+            .get(&url)
+            .header("Cookie", self.cookie())
+            .call()
+            .wrap_err_with(|| format!("requesting {}", url))?;
+        let body = response.body_mut().read_to_string()?;
+        match response.status() {
+            StatusCode::OK => Ok(body),
+            StatusCode::NOT_FOUND => bail!("Day {} input isn't available yet", day),
+            StatusCode::BAD_REQUEST | StatusCode::INTERNAL_SERVER_ERROR => bail!(
+                "Request for day {} input failed with {}. Is the session token still valid?",
+                day,
+                response.status()
+            ),
+            status => bail!("Request for day {} input failed with {}", day, status),
+        }
+    }
+
+    /// Submits `answer` for `day` and `part`, returning the response page.
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<String> {
+        self.throttle()?;
+        let url = format!("{}/answer", self.day_url(day));
+        debug!(url, part, answer, "submitting answer");
+        let level = part.to_string();
+        let mut response = self
+            .agent
+            .post(&url)
+            .header("Cookie", self.cookie())
+            .send_form([("level", level.as_str()), ("answer", answer)])
+            .wrap_err_with(|| format!("requesting {}", url))?;
+        let body = response.body_mut().read_to_string()?;
+        match response.status() {
+            StatusCode::OK => Ok(body),
+            status => bail!(
+                "Submitting day {} part {} failed with {}",
+                day,
+                part,
+                status
+            ),
+        }
+    }
+}
+
+/// Downloads the input for `day` to `path`, unless it already exists.
+pub fn fetch_input(client: &Client, day: u8, path: &Path) -> Result<()> {
+    if path.exists() {
+        info!("Day {} input already downloaded to {}", day, path.display());
+        return Ok(());
+    }
+    let input = client.input(day)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, input).wrap_err_with(|| format!("writing {}", path.display()))?;
+    info!("Saved day {} input to {}", day, path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{stub_server, temp_dir, test_client};
+    use test_log::test;
+
+    #[test]
+    fn test_fetch_input() {
+        let dir = temp_dir("fetch");
+        let (base_url, requests) = stub_server(vec![(200, "L68\nL30\n".to_string())]);
+        let path = dir.join("input.txt");
+        fetch_input(&test_client(&base_url, &dir), 1, &path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "L68\nL30\n");
+
+        let request = requests.recv().unwrap();
+        assert_eq!(request.request_line, "GET /2025/day/1/input HTTP/1.1");
+        assert!(request.body.is_empty());
+        assert!(
+            request
+                .headers
+                .contains(&"cookie: session=abc123".to_string())
+        );
+        assert!(
+            request
+                .headers
+                .contains(&format!("user-agent: {}", USER_AGENT))
+        );
+    }
+
+    #[test]
+    fn test_fetch_input_skips_existing_file() {
+        let dir = temp_dir("fetch-existing");
+        let path = dir.join("input.txt");
+        std::fs::write(&path, "existing").unwrap();
+        // Nothing is listening here, so any request would fail
+        let client = test_client("http://127.0.0.1:9", &dir);
+        fetch_input(&client, 1, &path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "existing");
+    }
+
+    #[test]
+    fn test_fetch_input_not_available() {
+        let dir = temp_dir("fetch-missing");
+        let (base_url, _requests) = stub_server(vec![(404, "Not Found".to_string())]);
+        let path = dir.join("input.txt");
+        let err = fetch_input(&test_client(&base_url, &dir), 12, &path).unwrap_err();
+        assert!(err.to_string().contains("isn't available yet"));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_throttle() {
+        let dir = temp_dir("throttle");
+        let client = Client::new("http://127.0.0.1:9", "abc123")
+            .with_throttle(dir.join("last-request"), Duration::from_millis(200));
+        client.throttle().unwrap();
+        let start = std::time::Instant::now();
+        client.throttle().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(150));
+    }
+}
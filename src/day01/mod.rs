@@ -1,106 +1,162 @@
 use std::str::FromStr;
 
-use color_eyre::{
-    Result,
-    eyre::{Error, eyre},
-};
+use color_eyre::Result;
 use tracing::{debug, instrument};
 
-pub const INPUT: &str = include_str!("input/input.txt");
+use crate::{
+    Solution,
+    parse::{ParseError, parse_number},
+};
+
+const LOCK_SIZE: i64 = 100;
+const LOCK_STARTING_POSITION: i64 = 50;
 
-const LOCK_SIZE: i32 = 100;
-const LOCK_STARTING_POSITION: i32 = 50;
+pub struct Day;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 enum Direction {
     Left,
     Right,
 }
 
 impl FromStr for Direction {
-    type Err = Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self> {
+    fn from_str(s: &str) -> Result<Self, ParseError> {
         match s {
             "L" => Ok(Direction::Left),
             "R" => Ok(Direction::Right),
-            _ => Err(eyre!("Invalid direction")),
+            _ => Err(ParseError::new("Invalid direction", s)),
         }
     }
 }
 
-#[instrument(skip(input))]
-pub fn part1(input: &str) -> Result<i32> {
-    let mut dial = LOCK_STARTING_POSITION;
-    let mut visited_zero_count = 0;
-    for line in input.trim().split('\n') {
-        let dir: &Direction = &line[0..1].parse()?;
-        let clicks: i32 = line[1..].parse()?;
-        match dir {
-            Direction::Left => {
-                debug!("Turn left {} clicks", clicks);
-                dial = (dial - clicks).rem_euclid(LOCK_SIZE);
-                debug!("Dial is now {}", dial);
+#[derive(Debug, Clone, Copy)]
+pub struct Rotation {
+    direction: Direction,
+    clicks: i64,
+}
+
+impl FromStr for Rotation {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, ParseError> {
+        let split = line.chars().next().map_or(0, char::len_utf8);
+        let (direction, clicks) = line.split_at(split);
+        let direction = direction
+            .parse()
+            .map_err(|err: ParseError| err.within(line, direction))?;
+        let clicks = parse_number::<u32>("click count", clicks)
+            .map_err(|err| err.within(line, clicks))?
+            .into();
+        Ok(Rotation { direction, clicks })
+    }
+}
+
+impl Solution for Day {
+    type Parsed = Vec<Rotation>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    #[instrument(skip(input))]
+    fn parse(input: &str) -> Result<Vec<Rotation>> {
+        let rotations = input
+            .trim()
+            .split('\n')
+            .map(|line| line.parse().map_err(|err: ParseError| err.within(input, line)))
+            .collect::<Result<_, _>>()?;
+        Ok(rotations)
+    }
+
+    /// Every line must be `L` or `R` followed by an unsigned click count.
+    fn validate(input: &str) -> Vec<ParseError> {
+        input
+            .trim()
+            .split('\n')
+            .filter_map(|line| {
+                let clicks = line.get(1..).unwrap_or_default();
+                let err = match line.parse::<Rotation>() {
+                    Err(err) => err,
+                    Ok(_) if !clicks.bytes().all(|b| b.is_ascii_digit()) => {
+                        ParseError::new("Click count must be unsigned", clicks).within(line, clicks)
+                    }
+                    Ok(_) => return None,
+                };
+                Some(err.within(input, line))
+            })
+            .collect()
+    }
+
+    #[instrument(skip(rotations))]
+    fn part1(rotations: &Vec<Rotation>) -> Result<i64> {
+        let mut dial = LOCK_STARTING_POSITION;
+        let mut visited_zero_count = 0;
+        for &Rotation { direction, clicks } in rotations {
+            match direction {
+                Direction::Left => {
+                    debug!("Turn left {} clicks", clicks);
+                    dial = (dial - clicks).rem_euclid(LOCK_SIZE);
+                    debug!("Dial is now {}", dial);
+                }
+                Direction::Right => {
+                    debug!("Turn right {} clicks", clicks);
+                    dial = (dial + clicks).rem_euclid(LOCK_SIZE);
+                    debug!("Dial is now {}", dial);
+                }
             }
-            Direction::Right => {
-                debug!("Turn right {} clicks", clicks);
-                dial = (dial + clicks).rem_euclid(LOCK_SIZE);
-                debug!("Dial is now {}", dial);
+            if dial == 0 {
+                visited_zero_count += 1;
+                debug!("Visited zero, count is now {}", visited_zero_count);
             }
         }
-        if dial == 0 {
-            visited_zero_count += 1;
-            debug!("Visited zero, count is now {}", visited_zero_count);
-        }
+        Ok(visited_zero_count)
     }
-    Ok(visited_zero_count)
-}
 
-#[instrument(skip(input))]
-pub fn part2(input: &str) -> Result<i32> {
-    let mut dial = LOCK_STARTING_POSITION;
-    let mut visited_zero_count = 0;
-    for line in input.trim().split('\n') {
-        let dir: &Direction = &line[0..1].parse()?;
-        let clicks: i32 = line[1..].parse()?;
-        match dir {
-            Direction::Left => {
-                debug!("Turn left {} clicks", clicks);
-                let end = dial - clicks;
-                let passing_zero_count =
-                    (dial - 1).div_euclid(LOCK_SIZE) - (end - 1).div_euclid(LOCK_SIZE);
-                if passing_zero_count > 0 {
-                    visited_zero_count += passing_zero_count;
-                    debug!(
-                        "Passed zero {} times, count is now {}",
-                        passing_zero_count, visited_zero_count
-                    );
+    #[instrument(skip(rotations))]
+    fn part2(rotations: &Vec<Rotation>) -> Result<i64> {
+        let mut dial = LOCK_STARTING_POSITION;
+        let mut visited_zero_count = 0;
+        for &Rotation { direction, clicks } in rotations {
+            match direction {
+                Direction::Left => {
+                    debug!("Turn left {} clicks", clicks);
+                    let end = dial - clicks;
+                    let passing_zero_count =
+                        (dial - 1).div_euclid(LOCK_SIZE) - (end - 1).div_euclid(LOCK_SIZE);
+                    if passing_zero_count > 0 {
+                        visited_zero_count += passing_zero_count;
+                        debug!(
+                            "Passed zero {} times, count is now {}",
+                            passing_zero_count, visited_zero_count
+                        );
+                    }
+                    dial = end.rem_euclid(LOCK_SIZE);
+                    debug!("Dial is now {}", dial);
                 }
-                dial = end.rem_euclid(LOCK_SIZE);
-                debug!("Dial is now {}", dial);
-            }
-            Direction::Right => {
-                debug!("Turn right {} clicks", clicks);
-                let end = dial + clicks;
-                let passing_zero_count = end.div_euclid(LOCK_SIZE);
-                if passing_zero_count > 0 {
-                    visited_zero_count += passing_zero_count;
-                    debug!(
-                        "Passed zero {} times, count is now {}",
-                        passing_zero_count, visited_zero_count
-                    );
+                Direction::Right => {
+                    debug!("Turn right {} clicks", clicks);
+                    let end = dial + clicks;
+                    let passing_zero_count = end.div_euclid(LOCK_SIZE);
+                    if passing_zero_count > 0 {
+                        visited_zero_count += passing_zero_count;
+                        debug!(
+                            "Passed zero {} times, count is now {}",
+                            passing_zero_count, visited_zero_count
+                        );
+                    }
+                    dial = end.rem_euclid(LOCK_SIZE);
+                    debug!("Dial is now {}", dial);
                 }
-                dial = end.rem_euclid(LOCK_SIZE);
-                debug!("Dial is now {}", dial);
             }
         }
+        Ok(visited_zero_count)
     }
-    Ok(visited_zero_count)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
     use test_log::test;
 
     const TEST_INPUT1: &str = include_str!("input/test1.txt");
@@ -120,11 +176,55 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT1).unwrap(), 3);
+        assert_eq!(Day::solve_part1(TEST_INPUT1).unwrap(), 3);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT1).unwrap(), 6);
+        assert_eq!(Day::solve_part2(TEST_INPUT1).unwrap(), 6);
+    }
+
+    /// Turns the dial one click at a time, returning how often it ended a rotation on zero and
+    /// how often it pointed at zero at all.
+    fn click_by_click(rotations: &[Rotation]) -> (i64, i64) {
+        let mut dial = LOCK_STARTING_POSITION;
+        let (mut ended_on_zero, mut passed_zero) = (0, 0);
+        for rotation in rotations {
+            let step = match rotation.direction {
+                Direction::Left => -1,
+                Direction::Right => 1,
+            };
+            for _ in 0..rotation.clicks {
+                dial = (dial + step).rem_euclid(LOCK_SIZE);
+                if dial == 0 {
+                    passed_zero += 1;
+                }
+            }
+            if dial == 0 {
+                ended_on_zero += 1;
+            }
+        }
+        (ended_on_zero, passed_zero)
+    }
+
+    fn rotations() -> impl Strategy<Value = Vec<Rotation>> {
+        let rotation = (any::<bool>(), 0..1000_i64).prop_map(|(left, clicks)| Rotation {
+            direction: if left {
+                Direction::Left
+            } else {
+                Direction::Right
+            },
+            clicks,
+        });
+        prop::collection::vec(rotation, 0..50)
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_click_by_click(rotations in rotations()) {
+            let (ended_on_zero, passed_zero) = click_by_click(&rotations);
+            prop_assert_eq!(Day::part1(&rotations).unwrap(), ended_on_zero);
+            prop_assert_eq!(Day::part2(&rotations).unwrap(), passed_zero);
+        }
     }
 }
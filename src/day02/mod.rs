@@ -1,33 +1,35 @@
 use std::{fmt::Display, str::FromStr};
 
-use color_eyre::{
-    Result,
-    eyre::{Error, eyre},
-};
+use color_eyre::{Result, eyre::OptionExt};
 use rayon::prelude::*;
-use tracing::{debug, debug_span, instrument};
+use tracing::{Span, debug, debug_span, instrument};
+
+use crate::{
+    Solution,
+    parse::{ParseError, parse_number},
+};
 
-pub const INPUT: &str = include_str!("input/input.txt");
+pub struct Day;
 
 #[derive(Clone, Debug)]
-struct ProductRange(std::ops::RangeInclusive<i64>);
+pub struct ProductRange(std::ops::RangeInclusive<i64>);
 
 impl FromStr for ProductRange {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut parts = s.split('-');
         let start = parts
             .next()
-            .ok_or(eyre!("Invalid product range: no start"))?
-            .parse::<i64>()?;
+            .ok_or_else(|| ParseError::new("Invalid product range: no start", s))?;
         let end = parts
             .next()
-            .ok_or(eyre!("Invalid product range: no end"))?
-            .parse::<i64>()?;
-        if parts.next().is_some() {
-            return Err(eyre!("Invalid product range: too many parts"));
+            .ok_or_else(|| ParseError::new("Invalid product range: no end", s))?;
+        if let Some(extra) = parts.next() {
+            return Err(ParseError::new("Invalid product range: too many parts", extra).within(s, extra));
         }
+        let start = parse_number("product range start", start).map_err(|err| err.within(s, start))?;
+        let end = parse_number("product range end", end).map_err(|err| err.within(s, end))?;
         Ok(ProductRange(start..=end))
     }
 }
@@ -50,12 +52,36 @@ impl Iterator for ProductRange {
     }
 }
 
+/// Adds `id` to the running `sum` of invalid IDs, failing if it no longer fits in an `i64`.
+fn add_id(sum: i64, id: i64) -> Result<i64> {
+    sum.checked_add(id).ok_or_eyre("sum of invalid IDs overflowed")
+}
+
+/// Sums the invalid IDs of every range on the rayon pool.
+fn sum_ranges(
+    ranges: &[ProductRange],
+    invalid_ids: fn(&ProductRange) -> Result<Vec<i64>>,
+) -> Result<i64> {
+    // Rayon jobs don't inherit the current span, so parent each range's span explicitly
+    let span = Span::current();
+    ranges
+        .par_iter()
+        .map(|range| {
+            let _span = debug_span!(parent: &span, "range", range = %range).entered();
+            invalid_ids(range)?.into_iter().try_fold(0, add_id)
+        })
+        .try_reduce(|| 0, add_id)
+}
+
 impl ProductRange {
-    fn invalid_ids(self) -> Result<Vec<i64>> {
+    fn invalid_ids(&self) -> Result<Vec<i64>> {
         let start = *self.0.start();
         let end = *self.0.end();
 
         let mut invalid_ids = Vec::new();
+        // Kept so an enormous range fails as soon as its sum overflows, rather than after listing
+        // every ID in it
+        let mut sum = 0;
 
         // Determine digit ranges we need to check
         let start_digits = if start == 0 { 1 } else { start.ilog10() + 1 };
@@ -74,8 +100,12 @@ impl ProductRange {
 
             // Generate all patterns where first half == second half
             for half in half_min..=half_max {
-                let id = half * multiplier;
+                // IDs too big for an i64 are past the end of the range
+                let Some(id) = half.checked_mul(multiplier) else {
+                    break;
+                };
                 if id >= start && id <= end {
+                    sum = add_id(sum, id)?;
                     invalid_ids.push(id);
                 }
             }
@@ -85,11 +115,12 @@ impl ProductRange {
         Ok(invalid_ids)
     }
 
-    fn invalid_ids2(self) -> Result<Vec<i64>> {
+    fn invalid_ids2(&self) -> Result<Vec<i64>> {
         let start = *self.0.start();
         let end = *self.0.end();
 
         let mut invalid_ids = std::collections::HashSet::new();
+        let mut sum = 0;
 
         // Determine digit ranges we need to check
         let start_digits = if start == 0 { 1 } else { start.ilog10() + 1 };
@@ -116,14 +147,20 @@ impl ProductRange {
                 // For ABCABC: chunk * (10^6 + 10^3 + 1) = chunk * 1001001
                 let mut multiplier = 0_i64;
                 for i in 0..num_chunks {
-                    multiplier += chunk_power.pow(i);
+                    multiplier = chunk_power
+                        .checked_pow(i)
+                        .and_then(|power| multiplier.checked_add(power))
+                        .ok_or_eyre("repeating pattern multiplier overflowed")?;
                 }
 
                 for chunk in chunk_min..=chunk_max {
-                    let id = chunk * multiplier;
+                    // IDs too big for an i64 are past the end of the range
+                    let Some(id) = chunk.checked_mul(multiplier) else {
+                        break;
+                    };
 
-                    if id >= start && id <= end {
-                        invalid_ids.insert(id);
+                    if id >= start && id <= end && invalid_ids.insert(id) {
+                        sum = add_id(sum, id)?;
                     }
                 }
             }
@@ -136,50 +173,100 @@ impl ProductRange {
     }
 }
 
-#[instrument(skip(input))]
-pub fn part1(input: &str) -> Result<i64> {
-    input
-        .trim()
-        .split(',')
-        .collect::<Vec<_>>()
-        .into_par_iter()
-        .map(|range| {
-            let _span = debug_span!("range", range = %range).entered();
-            let range: ProductRange = range.parse()?;
-            Ok(range.invalid_ids()?.iter().sum::<i64>())
-        })
-        .sum()
-}
+impl Solution for Day {
+    type Parsed = Vec<ProductRange>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    #[instrument(skip(input))]
+    fn parse(input: &str) -> Result<Vec<ProductRange>> {
+        let ranges = input
+            .trim()
+            .split(',')
+            .map(|range| range.parse().map_err(|err: ParseError| err.within(input, range)))
+            .collect::<Result<_, _>>()?;
+        Ok(ranges)
+    }
 
-#[instrument(skip(input))]
-pub fn part2(input: &str) -> Result<i64> {
-    input
-        .trim()
-        .split(',')
-        .collect::<Vec<_>>()
-        .into_par_iter()
-        .map(|range| {
-            let _span = debug_span!("range", range = %range).entered();
-            let range: ProductRange = range.parse()?;
-            Ok(range.invalid_ids2()?.iter().sum::<i64>())
-        })
-        .sum()
+    /// Every comma-separated range must parse and its start can't be after its end.
+    fn validate(input: &str) -> Vec<ParseError> {
+        input
+            .trim()
+            .split(',')
+            .filter_map(|range| {
+                let err = match range.parse::<ProductRange>() {
+                    Err(err) => err,
+                    Ok(ProductRange(ids)) if ids.start() > ids.end() => {
+                        ParseError::new("Range starts after it ends", range)
+                    }
+                    Ok(_) => return None,
+                };
+                Some(err.within(input, range))
+            })
+            .collect()
+    }
+
+    #[instrument(skip(ranges))]
+    fn part1(ranges: &Vec<ProductRange>) -> Result<i64> {
+        sum_ranges(ranges, ProductRange::invalid_ids)
+    }
+
+    #[instrument(skip(ranges))]
+    fn part2(ranges: &Vec<ProductRange>) -> Result<i64> {
+        sum_ranges(ranges, ProductRange::invalid_ids2)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use itertools::Itertools;
+    use proptest::prelude::*;
     use test_log::test;
 
     const TEST_INPUT1: &str = include_str!("input/test1.txt");
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT1).unwrap(), 1227775554);
+        assert_eq!(Day::solve_part1(TEST_INPUT1).unwrap(), 1227775554);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT1).unwrap(), 4174379265);
+        assert_eq!(Day::solve_part2(TEST_INPUT1).unwrap(), 4174379265);
+    }
+
+    /// Whether `id` is some sequence of digits repeated exactly `times` times, or at least twice
+    /// when `times` is `None`.
+    fn is_repeated(id: i64, times: Option<usize>) -> bool {
+        let digits = id.to_string();
+        (1..=digits.len() / 2).any(|chunk| {
+            digits.len().is_multiple_of(chunk)
+                && times.is_none_or(|times| digits.len() / chunk == times)
+                && digits.as_bytes().chunks(chunk).all_equal()
+        })
+    }
+
+    /// Sums the invalid IDs by checking every ID in every range.
+    fn scan_every_id(ranges: &[ProductRange], times: Option<usize>) -> i64 {
+        ranges
+            .iter()
+            .flat_map(|range| range.clone())
+            .filter(|&id| is_repeated(id, times))
+            .sum()
+    }
+
+    fn ranges() -> impl Strategy<Value = Vec<ProductRange>> {
+        let range = (1..10_000_000_000_i64, 0..2000_i64)
+            .prop_map(|(start, len)| ProductRange(start..=start + len));
+        prop::collection::vec(range, 1..5)
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_scanning_every_id(ranges in ranges()) {
+            prop_assert_eq!(Day::part1(&ranges).unwrap(), scan_every_id(&ranges, Some(2)));
+            prop_assert_eq!(Day::part2(&ranges).unwrap(), scan_every_id(&ranges, None));
+        }
     }
 }
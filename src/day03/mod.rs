@@ -1,30 +1,58 @@
-use color_eyre::Result;
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use color_eyre::{Result, eyre::bail};
 use tracing::{debug, instrument};
 
-pub const INPUT: &str = include_str!("input/input.txt");
+use crate::{Solution, parse::ParseError};
 
-#[derive(Debug, Clone, Copy)]
-struct Battery {
-    column: usize,
-    joltage: u8,
+pub struct Day;
+
+/// A row of battery joltages, one digit per battery.
+#[derive(Debug, Clone)]
+pub struct Bank(Vec<u8>);
+
+impl FromStr for Bank {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        line.char_indices()
+            .map(|(i, c)| match c.to_digit(10) {
+                Some(joltage) => Ok(joltage as u8),
+                None => Err(ParseError::new("Invalid joltage", c).at(line, i)),
+            })
+            .collect::<Result<_, _>>()
+            .map(Bank)
+    }
 }
 
-impl Default for Battery {
-    fn default() -> Self {
-        Battery {
-            column: 0,
-            joltage: 0,
+impl Display for Bank {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for joltage in &self.0 {
+            write!(f, "{}", joltage)?;
         }
+        Ok(())
     }
 }
 
-fn largest_output_joltage<const N: usize>(input: &str) -> Result<u64> {
+#[derive(Debug, Clone, Copy, Default)]
+struct Battery {
+    column: usize,
+    joltage: u8,
+}
+
+fn largest_output_joltage<const N: usize>(banks: &[Bank]) -> Result<u64> {
     let mut output_joltage: u64 = 0;
-    for line in input.trim().split('\n') {
+    for bank in banks {
         let mut batteries: [Battery; N] = [Battery::default(); N];
-        let line_len = line.len();
-        for (column, joltage) in line.bytes().map(|c| c - b'0').enumerate() {
-            let min = N.saturating_sub(line_len - column);
+        let bank_len = bank.0.len();
+        if bank_len < N {
+            bail!("Bank {} has fewer than {} batteries", bank, N);
+        }
+        for (column, &joltage) in bank.0.iter().enumerate() {
+            let min = N.saturating_sub(bank_len - column);
             for i in min..N {
                 if joltage > batteries[i].joltage {
                     batteries[i].column = column;
@@ -37,36 +65,105 @@ fn largest_output_joltage<const N: usize>(input: &str) -> Result<u64> {
         let line_joltage = batteries
             .iter()
             .fold(0u64, |acc, &b| acc * 10 + b.joltage as u64);
-        debug!(line, line_joltage);
+        debug!(bank = %bank, line_joltage);
         output_joltage += line_joltage;
     }
     Ok(output_joltage)
 }
 
-#[instrument(skip(input))]
-pub fn part1(input: &str) -> Result<u64> {
-    largest_output_joltage::<2>(input)
-}
+impl Solution for Day {
+    type Parsed = Vec<Bank>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    #[instrument(skip(input))]
+    fn parse(input: &str) -> Result<Vec<Bank>> {
+        let banks = input
+            .trim()
+            .split('\n')
+            .map(|line| line.parse().map_err(|err: ParseError| err.within(input, line)))
+            .collect::<Result<_, _>>()?;
+        Ok(banks)
+    }
+
+    /// Every battery must be a single digit, and part 2 needs at least 12 in each bank.
+    fn validate(input: &str) -> Vec<ParseError> {
+        let mut errors = Vec::new();
+        for line in input.trim().split('\n') {
+            for (i, c) in line.char_indices().filter(|(_, c)| !c.is_ascii_digit()) {
+                let text = &line[i..i + c.len_utf8()];
+                errors.push(ParseError::new("Invalid joltage", text).within(input, text));
+            }
+            let batteries = line.chars().filter(char::is_ascii_digit).count();
+            if batteries < 12 {
+                let message = format!("Bank has {} batteries, fewer than 12", batteries);
+                errors.push(ParseError::new(message, line).within(input, line));
+            }
+        }
+        errors
+    }
 
-#[instrument(skip(input))]
-pub fn part2(input: &str) -> Result<u64> {
-    largest_output_joltage::<12>(input)
+    #[instrument(skip(banks))]
+    fn part1(banks: &Vec<Bank>) -> Result<u64> {
+        largest_output_joltage::<2>(banks)
+    }
+
+    #[instrument(skip(banks))]
+    fn part2(banks: &Vec<Bank>) -> Result<u64> {
+        largest_output_joltage::<12>(banks)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use itertools::Itertools;
+    use proptest::prelude::*;
     use test_log::test;
 
     const TEST_INPUT1: &str = include_str!("input/test1.txt");
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT1).unwrap(), 357);
+        assert_eq!(Day::solve_part1(TEST_INPUT1).unwrap(), 357);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT1).unwrap(), 3121910778619);
+        assert_eq!(Day::solve_part2(TEST_INPUT1).unwrap(), 3121910778619);
+    }
+
+    /// The largest joltage from turning on `n` batteries, found by trying every subset.
+    fn every_subset(bank: &Bank, n: usize) -> u64 {
+        bank.0
+            .iter()
+            .combinations(n)
+            .map(|batteries| batteries.iter().fold(0, |acc, &&b| acc * 10 + b as u64))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn banks() -> impl Strategy<Value = Vec<Bank>> {
+        let bank = prop::collection::vec(0..=9_u8, 12..=16).prop_map(Bank);
+        prop::collection::vec(bank, 1..4)
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_every_subset(banks in banks()) {
+            let expected = |n| banks.iter().map(|bank| every_subset(bank, n)).sum::<u64>();
+            prop_assert_eq!(Day::part1(&banks).unwrap(), expected(2));
+            prop_assert_eq!(Day::part2(&banks).unwrap(), expected(12));
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(Day::validate(TEST_INPUT1).is_empty());
+        let errors = Day::validate("1234567890123\n12-4");
+        let positions: Vec<_> = errors.iter().map(ParseError::position).collect();
+        assert_eq!(positions, [Some((2, 3)), Some((2, 1))]);
+        let errors = Day::validate("12345678901é");
+        assert_eq!(errors[1].message(), "Bank has 11 batteries, fewer than 12");
     }
 }
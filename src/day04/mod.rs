@@ -3,10 +3,15 @@ use std::{
     str::FromStr,
 };
 
-use color_eyre::{Result, eyre::eyre};
+use color_eyre::Result;
 use tracing::{debug, instrument};
 
-pub const INPUT: &str = include_str!("input/input.txt");
+use crate::{
+    Solution,
+    parse::{ParseError, invalid_chars, ragged_rows},
+};
+
+pub struct Day;
 
 const ADJACENT_DELTAS: [(isize, isize); 8] = [
     (-1, -1),
@@ -27,53 +32,67 @@ enum Cell {
 }
 
 impl FromStr for Cell {
-    type Err = color_eyre::Report;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "." => Ok(Cell::Empty),
             "@" => Ok(Cell::Paper),
             "x" => Ok(Cell::AccessiblePaper),
-            _ => Err(eyre!("Invalid cell character: {}", s)),
+            _ => Err(ParseError::new("Invalid cell", s)),
         }
     }
 }
 
 impl Cell {
-    fn from_byte(b: u8) -> Result<Self, color_eyre::Report> {
-        match b {
-            b'.' => Ok(Cell::Empty),
-            b'@' => Ok(Cell::Paper),
-            b'x' => Ok(Cell::AccessiblePaper),
-            _ => Err(eyre!("Invalid cell byte: {}", b)),
+    fn from_char(c: char) -> Result<Self, ParseError> {
+        match c {
+            '.' => Ok(Cell::Empty),
+            '@' => Ok(Cell::Paper),
+            'x' => Ok(Cell::AccessiblePaper),
+            _ => Err(ParseError::new("Invalid cell", c)),
         }
     }
 }
 
-struct Grid<const R: usize, const C: usize> {
-    cells: [[Cell; C]; R],
+#[derive(Clone)]
+pub struct Grid {
+    cells: Vec<Vec<Cell>>,
+    rows: usize,
+    cols: usize,
 }
 
-impl<const R: usize, const C: usize> FromStr for Grid<R, C> {
-    type Err = color_eyre::Report;
+impl FromStr for Grid {
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut cells = [[Cell::Empty; C]; R];
-
-        for (row, line) in s.lines().enumerate() {
-            for (col, byte) in line.bytes().enumerate() {
-                cells[row][col] = Cell::from_byte(byte)?;
-            }
+        let mut cells = s
+            .lines()
+            .map(|line| {
+                line.char_indices()
+                    .map(|(i, c)| Cell::from_char(c).map_err(|err| err.at(line, i).within(s, line)))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // Pad short lines so every row has the same width
+        let rows = cells.len();
+        let cols = cells.iter().map(Vec::len).max().unwrap_or(0);
+        for row in &mut cells {
+            row.resize(cols, Cell::Empty);
         }
 
-        Ok(Grid { cells })
+        Ok(Grid {
+            cells,
+            rows,
+            cols,
+        })
     }
 }
 
-impl<const R: usize, const C: usize> Display for Grid<R, C> {
+impl Display for Grid {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for row in 0..R {
-            for col in 0..C {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
                 let symbol = match self.cells[row][col] {
                     Cell::Empty => '.',
                     Cell::Paper => '@',
@@ -87,11 +106,11 @@ impl<const R: usize, const C: usize> Display for Grid<R, C> {
     }
 }
 
-impl<const R: usize, const C: usize> Grid<R, C> {
+impl Grid {
     fn count_accessible_papers(&mut self, replace_with: Cell) -> usize {
         let mut count = 0;
-        for row in 0..R {
-            for col in 0..C {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
                 if self.cells[row][col] != Cell::Paper {
                     continue;
                 }
@@ -101,8 +120,8 @@ impl<const R: usize, const C: usize> Grid<R, C> {
                     let adj_col = col as isize + dc;
                     if adj_row >= 0
                         && adj_col >= 0
-                        && (adj_row as usize) < R
-                        && (adj_col as usize) < C
+                        && (adj_row as usize) < self.rows
+                        && (adj_col as usize) < self.cols
                     {
                         let adjacent = self.cells[adj_row as usize][adj_col as usize];
                         if matches!(adjacent, Cell::Paper | Cell::AccessiblePaper) {
@@ -120,22 +139,15 @@ impl<const R: usize, const C: usize> Grid<R, C> {
     }
 }
 
-fn solve_part1<const R: usize, const C: usize>(input: &str) -> Result<usize> {
-    let mut grid = Grid::<R, C>::from_str(input)?;
-    debug!("Parsed grid:\n{}", grid);
+fn solve_part1(grid: &Grid) -> Result<usize> {
+    let mut grid = grid.clone();
     let count = grid.count_accessible_papers(Cell::AccessiblePaper);
     debug!("Processed grid:\n{}", grid);
     Ok(count)
 }
 
-#[instrument(skip(input))]
-pub fn part1(input: &str) -> Result<usize> {
-    solve_part1::<135, 135>(input)
-}
-
-fn solve_part2<const R: usize, const C: usize>(input: &str) -> Result<usize> {
-    let mut grid = Grid::<R, C>::from_str(input)?;
-    debug!("Parsed grid:\n{}", grid);
+fn solve_part2(grid: &Grid) -> Result<usize> {
+    let mut grid = grid.clone();
     let mut count = 0;
     loop {
         let removed = grid.count_accessible_papers(Cell::Empty);
@@ -148,9 +160,35 @@ fn solve_part2<const R: usize, const C: usize>(input: &str) -> Result<usize> {
     Ok(count)
 }
 
-#[instrument(skip(input))]
-pub fn part2(input: &str) -> Result<usize> {
-    solve_part2::<135, 135>(input)
+impl Solution for Day {
+    type Parsed = Grid;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    #[instrument(skip(input))]
+    fn parse(input: &str) -> Result<Grid> {
+        let grid = input.parse()?;
+        debug!("Parsed grid:\n{}", grid);
+        Ok(grid)
+    }
+
+    /// The grid must be rectangular and only hold `.`, `@` and `x` (paper already marked as
+    /// accessible, as the debug output shows it).
+    fn validate(input: &str) -> Vec<ParseError> {
+        let mut errors = invalid_chars(input, "Invalid cell", |c| Cell::from_char(c).is_ok());
+        errors.extend(ragged_rows(input));
+        errors
+    }
+
+    #[instrument(skip(grid))]
+    fn part1(grid: &Grid) -> Result<usize> {
+        solve_part1(grid)
+    }
+
+    #[instrument(skip(grid))]
+    fn part2(grid: &Grid) -> Result<usize> {
+        solve_part2(grid)
+    }
 }
 
 #[cfg(test)]
@@ -162,11 +200,13 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(solve_part1::<10, 10>(TEST_INPUT1).unwrap(), 13);
+        let grid = TEST_INPUT1.parse::<Grid>().unwrap();
+        assert_eq!(solve_part1(&grid).unwrap(), 13);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(solve_part2::<10, 10>(TEST_INPUT1).unwrap(), 43);
+        let grid = TEST_INPUT1.parse::<Grid>().unwrap();
+        assert_eq!(solve_part2(&grid).unwrap(), 43);
     }
 }
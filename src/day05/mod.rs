@@ -1,79 +1,167 @@
 use std::{fmt::Display, str::FromStr};
 
-use color_eyre::{
-    Result,
-    eyre::{Error, eyre},
-};
+use color_eyre::{Result, eyre::OptionExt};
 use tracing::{debug, instrument};
 
-pub const INPUT: &str = include_str!("input/input.txt");
+use crate::{
+    Solution, Variant,
+    parse::{ParseError, parse_number},
+};
+
+pub struct Day;
 
 #[derive(Clone, Debug)]
-struct FreshRange(pub std::ops::RangeInclusive<i64>);
+pub struct FreshRange(pub std::ops::RangeInclusive<i64>);
 
 impl FromStr for FreshRange {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut parts = s.split('-');
         let start = parts
             .next()
-            .ok_or(eyre!("Invalid fresh range: no start"))?
-            .parse::<i64>()?;
+            .ok_or_else(|| ParseError::new("Invalid fresh range: no start", s))?;
         let end = parts
             .next()
-            .ok_or(eyre!("Invalid fresh range: no end"))?
-            .parse::<i64>()?;
-        if parts.next().is_some() {
-            return Err(eyre!("Invalid fresh range: too many parts"));
+            .ok_or_else(|| ParseError::new("Invalid fresh range: no end", s))?;
+        if let Some(extra) = parts.next() {
+            return Err(ParseError::new("Invalid fresh range: too many parts", extra).within(s, extra));
+        }
+        let start = parse_number("fresh range start", start).map_err(|err| err.within(s, start))?;
+        let end = parse_number("fresh range end", end).map_err(|err| err.within(s, end))?;
+        if start > end {
+            return Err(ParseError::new("Range starts after it ends", s));
         }
         Ok(FreshRange(start..=end))
     }
 }
 
+impl FreshRange {
+    /// How many IDs the range covers.
+    fn len(&self) -> Option<usize> {
+        usize::try_from(i128::from(*self.0.end()) - i128::from(*self.0.start()) + 1).ok()
+    }
+}
+
+/// How many IDs `ranges` cover between them, given that none of them overlap.
+fn count_ids<'a>(ranges: impl IntoIterator<Item = &'a FreshRange>) -> Result<usize> {
+    ranges
+        .into_iter()
+        .try_fold(0usize, |total, range| total.checked_add(range.len()?))
+        .ok_or_eyre("fresh ID count overflowed")
+}
+
 impl Display for FreshRange {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}-{}", self.0.start(), self.0.end())
     }
 }
 
-#[instrument(skip(input))]
-pub fn part1(input: &str) -> Result<usize> {
-    let mut processing_ranges = true;
-    let mut fresh_ranges = Vec::new();
-    let mut fresh_ingredients = 0;
-    for line in input.trim().lines() {
-        if line.is_empty() {
-            processing_ranges = false;
-            continue;
+/// The fresh ingredient ID ranges followed by the available ingredient IDs.
+#[derive(Clone, Debug)]
+pub struct Inventory {
+    fresh_ranges: Vec<FreshRange>,
+    ingredients: Vec<i64>,
+}
+
+impl Solution for Day {
+    type Parsed = Inventory;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    #[instrument(skip(input))]
+    fn parse(input: &str) -> Result<Inventory> {
+        let mut processing_ranges = true;
+        let mut fresh_ranges = Vec::new();
+        let mut ingredients = Vec::new();
+        for line in input.trim().lines() {
+            if line.is_empty() {
+                processing_ranges = false;
+                continue;
+            }
+            if processing_ranges {
+                let range = line
+                    .parse::<FreshRange>()
+                    .map_err(|err| err.within(input, line))?;
+                debug!(range = %range);
+                fresh_ranges.push(range);
+            } else {
+                let ingredient = parse_number::<i64>("ingredient ID", line)
+                    .map_err(|err| err.within(input, line))?;
+                debug!(ingredient);
+                ingredients.push(ingredient);
+            }
+        }
+        Ok(Inventory {
+            fresh_ranges,
+            ingredients,
+        })
+    }
+
+    /// Every range must parse and not start after it ends, and a blank line must separate them
+    /// from the ingredient IDs.
+    fn validate(input: &str) -> Vec<ParseError> {
+        let mut errors = Vec::new();
+        let mut processing_ranges = true;
+        for line in input.trim().lines() {
+            if line.is_empty() {
+                processing_ranges = false;
+                continue;
+            }
+            let error = if processing_ranges {
+                match line.parse::<FreshRange>() {
+                    Err(err) => err,
+                    Ok(_) => continue,
+                }
+            } else {
+                match parse_number::<i64>("ingredient ID", line) {
+                    Err(err) => err,
+                    Ok(_) => continue,
+                }
+            };
+            errors.push(error.within(input, line));
         }
         if processing_ranges {
-            let range = line.parse::<FreshRange>()?;
-            debug!(range = %range);
-            fresh_ranges.push(range);
-        } else {
-            let ingredient = line.parse::<i64>()?;
-            debug!(ingredient);
-            for range in &mut fresh_ranges {
-                if range.0.contains(&ingredient) {
+            errors.push(ParseError::new(
+                "No blank line between the fresh ranges and the ingredient IDs",
+                "",
+            ));
+        }
+        errors
+    }
+
+    #[instrument(skip(inventory))]
+    fn part1(inventory: &Inventory) -> Result<usize> {
+        let mut fresh_ingredients = 0;
+        for ingredient in &inventory.ingredients {
+            for range in &inventory.fresh_ranges {
+                if range.0.contains(ingredient) {
                     fresh_ingredients += 1;
-                    debug!(fresh_ingredients, "fresh!");
+                    debug!(ingredient, fresh_ingredients, "fresh!");
                     break;
                 }
             }
         }
+        Ok(fresh_ingredients)
+    }
+
+    #[instrument(skip(inventory))]
+    fn part2(inventory: &Inventory) -> Result<usize> {
+        count_fresh_sorted(inventory)
+    }
+
+    fn part2_variants() -> Vec<Variant<Inventory, usize>> {
+        vec![Variant {
+            name: "naive",
+            solve: count_fresh_naive,
+        }]
     }
-    Ok(fresh_ingredients)
 }
 
-#[instrument(skip(input))]
-pub fn part2(input: &str) -> Result<usize> {
+/// Merges each range into every earlier range it overlaps, which is O(n²) in the number of ranges.
+fn count_fresh_naive(inventory: &Inventory) -> Result<usize> {
     let mut fresh_ranges: Vec<Option<FreshRange>> = Vec::new();
-    for line in input.trim().lines() {
-        if line.is_empty() {
-            break;
-        }
-        let range = line.parse::<FreshRange>()?;
+    for range in &inventory.fresh_ranges {
         let mut overlap_range = range.clone();
         debug!(range = %range);
         for range_slot in fresh_ranges.iter_mut() {
@@ -94,11 +182,26 @@ pub fn part2(input: &str) -> Result<usize> {
         }
         fresh_ranges.push(Some(overlap_range));
     }
-    Ok(fresh_ranges
-        .iter()
-        .flatten()
-        .map(|r| (r.0.end() - r.0.start() + 1) as usize)
-        .sum())
+    count_ids(fresh_ranges.iter().flatten())
+}
+
+/// Sorts the ranges by start so overlapping ranges are adjacent, then merges them in one pass.
+fn count_fresh_sorted(inventory: &Inventory) -> Result<usize> {
+    let mut ranges = inventory.fresh_ranges.clone();
+    ranges.sort_unstable_by_key(|r| (*r.0.start(), *r.0.end()));
+    let mut merged: Vec<FreshRange> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if *range.0.start() <= last.0.end().saturating_add(1) => {
+                if range.0.end() > last.0.end() {
+                    *last = FreshRange(*last.0.start()..=*range.0.end());
+                }
+            }
+            _ => merged.push(range),
+        }
+    }
+    debug!(merged = merged.len());
+    count_ids(&merged)
 }
 
 #[cfg(test)]
@@ -110,16 +213,25 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT1).unwrap(), 3);
+        assert_eq!(Day::solve_part1(TEST_INPUT1).unwrap(), 3);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT1).unwrap(), 14);
+        assert_eq!(Day::solve_part2(TEST_INPUT1).unwrap(), 14);
     }
 
     #[test]
     fn test_part2_triple_overlap() {
-        assert_eq!(part2("3-4\n2-5\n1-6").unwrap(), 6);
+        assert_eq!(Day::solve_part2("3-4\n2-5\n1-6").unwrap(), 6);
+    }
+
+    #[test]
+    fn test_part2_variants() {
+        for input in [TEST_INPUT1, "3-4\n2-5\n1-6", "1-2\n3-4\n10-20\n5-9"] {
+            let inventory = Day::parse(input).unwrap();
+            let expected = count_fresh_naive(&inventory).unwrap();
+            assert_eq!(count_fresh_sorted(&inventory).unwrap(), expected);
+        }
     }
 }
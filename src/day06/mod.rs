@@ -1,13 +1,15 @@
 use std::str::FromStr;
 
-use color_eyre::{
-    Result,
-    eyre::{Context, Error, OptionExt, eyre},
-};
+use color_eyre::{Result, eyre::OptionExt};
 use itertools::Itertools;
 use tracing::{debug, instrument};
 
-pub const INPUT: &str = include_str!("input/input.txt");
+use crate::{
+    Solution,
+    parse::{ParseError, parse_number},
+};
+
+pub struct Day;
 
 #[derive(Debug, Clone, Copy)]
 enum Operation {
@@ -16,23 +18,39 @@ enum Operation {
 }
 
 impl FromStr for Operation {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "+" => Ok(Operation::Add),
             "*" => Ok(Operation::Multiply),
-            _ => Err(eyre!("invalid operation: {}", s)),
+            _ => Err(ParseError::new("Invalid operation", s)),
         }
     }
 }
 
 impl Operation {
-    fn from_byte(byte: u8) -> Result<Self> {
-        match byte {
-            b'+' => Ok(Operation::Add),
-            b'*' => Ok(Operation::Multiply),
-            _ => Err(eyre!("invalid operation byte: {}", byte)),
+    fn from_char(c: char) -> Result<Self, ParseError> {
+        match c {
+            '+' => Ok(Operation::Add),
+            '*' => Ok(Operation::Multiply),
+            _ => Err(ParseError::new("Invalid operation", c)),
+        }
+    }
+
+    /// Adds or multiplies `numbers`, or `None` if the result doesn't fit in a `u64`.
+    fn apply(self, numbers: impl IntoIterator<Item = u64>) -> Option<u64> {
+        let mut numbers = numbers.into_iter();
+        match self {
+            Operation::Add => numbers.try_fold(0u64, u64::checked_add),
+            Operation::Multiply => numbers.try_fold(1u64, u64::checked_mul),
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Operation::Add => " + ",
+            Operation::Multiply => " * ",
         }
     }
 }
@@ -47,18 +65,40 @@ struct Problem {
 struct CephalopodProblem {
     columns: Vec<Vec<u8>>,
     operation: Operation,
-    width: u8,
+    width: usize,
+}
+
+impl CephalopodProblem {
+    /// The number in each column, read top to bottom, or `None` if one doesn't fit in a `u64`.
+    fn numbers(&self) -> Option<Vec<u64>> {
+        self.columns
+            .iter()
+            .map(|col| {
+                col.iter().try_fold(0u64, |acc, &digit| {
+                    acc.checked_mul(10)?.checked_add(u64::from(digit))
+                })
+            })
+            .collect()
+    }
+}
+
+/// The worksheet read both ways: row-wise for part 1 and column-wise (cephalopod math) for part 2.
+/// Each reading keeps its own parse result, so a layout that only one part can't read fails just
+/// that part.
+#[derive(Debug, Clone)]
+pub struct Worksheet {
+    problems: Result<Vec<Problem>, ParseError>,
+    cephalopod_problems: Result<Vec<CephalopodProblem>, ParseError>,
 }
 
-#[instrument(skip(input))]
-pub fn part1(input: &str) -> Result<u64> {
+fn parse_problems(input: &str) -> Result<Vec<Problem>, ParseError> {
     let mut lines = input.trim().lines();
     let first_numbers: Vec<u64> = lines
         .next()
-        .ok_or_eyre("no first line in input")?
+        .ok_or_else(|| ParseError::new("Empty worksheet", ""))?
         .split_whitespace()
-        .map(|s| s.parse::<u64>().context("parsing number"))
-        .collect::<Result<Vec<u64>>>()?;
+        .map(|s| parse_number("number", s).map_err(|err| err.within(input, s)))
+        .collect::<Result<_, _>>()?;
     let mut problems = first_numbers
         .into_iter()
         .map(|n| Problem {
@@ -67,130 +107,172 @@ pub fn part1(input: &str) -> Result<u64> {
         })
         .collect::<Vec<Problem>>();
     for line in lines {
-        let first_byte = line.bytes().nth(0).ok_or_eyre("empty line in input")?;
+        let first_byte = line
+            .as_bytes()
+            .first()
+            .ok_or_else(|| ParseError::new("Empty line", "").within(input, line))?;
+        let fields = line.split_whitespace().count();
+        if fields != problems.len() {
+            let message = format!(
+                "Row has {} fields, but the first row has {}",
+                fields,
+                problems.len()
+            );
+            return Err(ParseError::new(message, line).within(input, line));
+        }
         if matches!(first_byte, b'*' | b'+') {
-            for (index, op_result) in line.split_whitespace().map(|s| s.parse()).enumerate() {
-                let op = op_result?;
-                problems[index].operation = op;
+            for (problem, s) in problems.iter_mut().zip(line.split_whitespace()) {
+                let op = s
+                    .parse()
+                    .map_err(|err: ParseError| err.within(input, s))?;
+                problem.operation = op;
             }
         } else {
-            for (index, num_result) in line.split_whitespace().map(|s| s.parse()).enumerate() {
-                let num = num_result?;
-                problems[index].numbers.push(num);
+            for (problem, s) in problems.iter_mut().zip(line.split_whitespace()) {
+                let num = parse_number("number", s).map_err(|err| err.within(input, s))?;
+                problem.numbers.push(num);
             }
         }
     }
-    Ok(problems
-        .into_iter()
-        .map(|problem| match problem.operation {
-            Operation::Add => {
-                let sum = problem.numbers.iter().sum::<u64>();
-                debug!("{} = {}", problem.numbers.iter().join(" + "), sum);
-                return sum;
-            }
-            Operation::Multiply => {
-                let product = problem.numbers.iter().product::<u64>();
-                debug!("{} = {}", problem.numbers.iter().join(" * "), product);
-                return product;
-            }
-        })
-        .sum())
+    Ok(problems)
 }
 
-#[instrument(skip(input))]
-pub fn part2(input: &str) -> Result<usize> {
+fn parse_cephalopod_problems(input: &str) -> Result<Vec<CephalopodProblem>, ParseError> {
     let mut lines = input.lines();
-    let last_line = lines.next_back().ok_or_eyre("no last line in input")?;
+    let last_line = lines
+        .next_back()
+        .ok_or_else(|| ParseError::new("Empty worksheet", ""))?;
     let mut problems = Vec::new();
-    let mut spaces: u8 = 1;
-    for byte in last_line.bytes().rev() {
-        if byte.is_ascii_whitespace() {
+    let mut spaces = 1;
+    for (col, c) in last_line.char_indices().rev() {
+        if c.is_ascii_whitespace() {
             spaces += 1;
         } else {
             problems.push(CephalopodProblem {
                 columns: (0..spaces).map(|_| Vec::new()).collect(),
-                operation: Operation::from_byte(byte)?,
+                operation: Operation::from_char(c)
+                    .map_err(|err| err.at(last_line, col).within(input, last_line))?,
                 width: spaces,
             });
             spaces = 0;
         }
     }
-    debug!(last_problem = ?problems[0]);
-    debug!(second_last_problem = ?problems[1]);
     problems.reverse();
     for line in lines {
         let bytes = line.as_bytes();
         let mut offset = 0;
-        for i in 0..problems.len() {
-            if offset + (problems[i].width as usize) > bytes.len() {
-                return Err(eyre!("line too short for problem columns"));
+        for problem in &mut problems {
+            if offset + problem.width > bytes.len() {
+                let err = ParseError::new("Line too short for problem columns", line);
+                return Err(err.within(input, line));
             }
-            for (cell_col, &byte) in bytes[offset..offset + problems[i].width as usize]
-                .iter()
-                .enumerate()
-            {
-                if !byte.is_ascii_whitespace() {
-                    problems[i].columns[cell_col].push(byte - b'0');
+            for (cell_col, &byte) in bytes[offset..offset + problem.width].iter().enumerate() {
+                if byte.is_ascii_digit() {
+                    problem.columns[cell_col].push(byte - b'0');
+                } else if !byte.is_ascii_whitespace() {
+                    let err = ParseError::at_char("Invalid digit", line, offset + cell_col);
+                    return Err(err.within(input, line));
                 }
             }
-            offset += problems[i].width as usize + 1;
+            offset += problem.width + 1;
         }
     }
-    debug!(first_problem = ?problems[0]);
-    debug!(second_problem = ?problems[1]);
-    Ok(problems
-        .iter()
-        .map(|problem| match problem.operation {
-            Operation::Add => {
-                let sum: usize = problem
-                    .columns
-                    .iter()
-                    .map(|col| {
-                        col.iter()
-                            .fold(0usize, |acc, &digit| acc * 10 + (digit as usize))
-                    })
-                    .sum();
-                debug!(
-                    "{} = {}",
-                    problem
-                        .columns
-                        .iter()
-                        .map(|col| {
-                            col.iter()
-                                .fold(0usize, |acc, &digit| acc * 10 + (digit as usize))
-                                .to_string()
-                        })
-                        .join(" + "),
-                    sum
+    debug!(first_problem = ?problems.first(), second_problem = ?problems.get(1));
+    Ok(problems)
+}
+
+impl Solution for Day {
+    type Parsed = Worksheet;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    /// Fails only if neither part can read the worksheet.
+    #[instrument(skip(input))]
+    fn parse(input: &str) -> Result<Worksheet> {
+        let worksheet = Worksheet {
+            problems: parse_problems(input),
+            cephalopod_problems: parse_cephalopod_problems(input),
+        };
+        if let (Err(err), Err(_)) = (&worksheet.problems, &worksheet.cephalopod_problems) {
+            return Err(err.clone().into());
+        }
+        Ok(worksheet)
+    }
+
+    /// The operators must be on the last line only, and every row must have a number for each
+    /// operator and be as wide as the operator row so the columns line up.
+    fn validate(input: &str) -> Vec<ParseError> {
+        let lines = input.trim_matches('\n').lines().collect::<Vec<_>>();
+        let Some((operators, rows)) = lines.split_last() else {
+            return vec![ParseError::new("Empty worksheet", "")];
+        };
+        let mut errors = operators
+            .split_whitespace()
+            .filter_map(|s| s.parse::<Operation>().err().map(|err| err.within(input, s)))
+            .collect::<Vec<_>>();
+        let problems = operators.split_whitespace().count();
+        for &row in rows {
+            let numbers = row.split_whitespace().collect::<Vec<_>>();
+            if numbers.first().is_some_and(|s| s.parse::<Operation>().is_ok()) {
+                let err = ParseError::new("Operators are not on the last line", row);
+                errors.push(err.within(input, row));
+                continue;
+            }
+            errors.extend(numbers.iter().filter_map(|s| {
+                parse_number::<u64>("number", s)
+                    .err()
+                    .map(|err| err.within(input, s))
+            }));
+            if numbers.len() != problems {
+                let message = format!(
+                    "Row has {} numbers, but there are {} operators",
+                    numbers.len(),
+                    problems
                 );
-                sum
+                errors.push(ParseError::new(message, row).within(input, row));
             }
-            Operation::Multiply => {
-                let product: usize = problem
-                    .columns
-                    .iter()
-                    .map(|col| {
-                        col.iter()
-                            .fold(0usize, |acc, &digit| acc * 10 + (digit as usize))
-                    })
-                    .product();
-                debug!(
-                    "{} = {}",
-                    problem
-                        .columns
-                        .iter()
-                        .map(|col| {
-                            col.iter()
-                                .fold(0usize, |acc, &digit| acc * 10 + (digit as usize))
-                                .to_string()
-                        })
-                        .join(" * "),
-                    product
+            if row.len() != operators.len() {
+                let message = format!(
+                    "Row is {} wide, but the operator row is {}",
+                    row.len(),
+                    operators.len()
                 );
-                product
+                errors.push(ParseError::new(message, row).within(input, row));
             }
+        }
+        errors
+    }
+
+    #[instrument(skip(worksheet))]
+    fn part1(worksheet: &Worksheet) -> Result<u64> {
+        let problems = worksheet.problems.as_ref().map_err(Clone::clone)?;
+        problems.iter().try_fold(0u64, |total, problem| {
+            let result = problem
+                .operation
+                .apply(problem.numbers.iter().copied())
+                .ok_or_eyre("problem result overflowed")?;
+            debug!(
+                "{} = {}",
+                problem.numbers.iter().join(problem.operation.symbol()),
+                result
+            );
+            total.checked_add(result).ok_or_eyre("grand total overflowed")
         })
-        .sum())
+    }
+
+    #[instrument(skip(worksheet))]
+    fn part2(worksheet: &Worksheet) -> Result<u64> {
+        let problems = worksheet.cephalopod_problems.as_ref().map_err(Clone::clone)?;
+        problems.iter().try_fold(0u64, |total, problem| {
+            let numbers = problem.numbers().ok_or_eyre("column number overflowed")?;
+            let result = problem
+                .operation
+                .apply(numbers.iter().copied())
+                .ok_or_eyre("problem result overflowed")?;
+            debug!("{} = {}", numbers.iter().join(problem.operation.symbol()), result);
+            total.checked_add(result).ok_or_eyre("grand total overflowed")
+        })
+    }
 }
 
 #[cfg(test)]
@@ -202,11 +284,37 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT1).unwrap(), 4277556);
+        assert_eq!(Day::solve_part1(TEST_INPUT1).unwrap(), 4277556);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT1).unwrap(), 3263827);
+        assert_eq!(Day::solve_part2(TEST_INPUT1).unwrap(), 3263827);
+    }
+
+    #[test]
+    fn test_layouts_fail_independently() {
+        // The rows are too short for the operator columns, which only part 2 reads
+        let worksheet = Day::parse("1 2\n3 4\n+  *").unwrap();
+        assert_eq!(Day::part1(&worksheet).unwrap(), 12);
+        let err = Day::part2(&worksheet).unwrap_err();
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(err.message(), "Line too short for problem columns");
+        assert_eq!(err.position(), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(Day::validate(TEST_INPUT1).is_empty());
+        let errors = Day::validate("1 2\n+ *\n3 x\n* +");
+        let messages: Vec<_> = errors.iter().map(ParseError::message).collect();
+        assert_eq!(
+            messages,
+            [
+                "Operators are not on the last line",
+                "Invalid number (invalid digit found in string)",
+            ]
+        );
+        assert_eq!(errors[1].position(), Some((3, 3)));
     }
 }
@@ -3,10 +3,18 @@ use std::{
     str::FromStr,
 };
 
-use color_eyre::{Result, eyre::eyre};
+use color_eyre::{
+    Result,
+    eyre::{OptionExt, bail},
+};
 use tracing::{debug, instrument};
 
-pub const INPUT: &str = include_str!("input/input.txt");
+use crate::{
+    Solution,
+    parse::{ParseError, invalid_chars, ragged_rows},
+};
+
+pub struct Day;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Cell {
@@ -17,7 +25,7 @@ enum Cell {
 }
 
 impl FromStr for Cell {
-    type Err = color_eyre::Report;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -25,52 +33,77 @@ impl FromStr for Cell {
             "^" => Ok(Cell::Splitter),
             "|" => Ok(Cell::Beam(1)),
             "." => Ok(Cell::Empty),
-            _ => Err(eyre!("Invalid cell character: {}", s)),
+            _ => Err(ParseError::new("Invalid cell", s)),
         }
     }
 }
 
 impl Cell {
-    fn from_byte(b: u8) -> Result<Self, color_eyre::Report> {
-        match b {
-            b'S' => Ok(Cell::Source),
-            b'^' => Ok(Cell::Splitter),
-            b'|' => Ok(Cell::Beam(1)),
-            b'.' => Ok(Cell::Empty),
-            _ => Err(eyre!("Invalid cell byte: {}", b)),
+    fn from_char(c: char) -> Result<Self, ParseError> {
+        match c {
+            'S' => Ok(Cell::Source),
+            '^' => Ok(Cell::Splitter),
+            '|' => Ok(Cell::Beam(1)),
+            '.' => Ok(Cell::Empty),
+            _ => Err(ParseError::new("Invalid cell", c)),
+        }
+    }
+
+    /// How many timelines a beam leaving this cell downwards carries.
+    fn timelines(self) -> usize {
+        match self {
+            Cell::Source => 1,
+            Cell::Beam(t) => t,
+            _ => 0,
         }
     }
 }
 
-struct Grid<const R: usize, const C: usize> {
-    cells: [[Cell; C]; R],
+#[derive(Clone)]
+pub struct Grid {
+    cells: Vec<Vec<Cell>>,
+    rows: usize,
+    cols: usize,
     splits: usize,
 }
 
-impl<const R: usize, const C: usize> FromStr for Grid<R, C> {
-    type Err = color_eyre::Report;
+impl FromStr for Grid {
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut cells = [[Cell::Empty; C]; R];
-
-        for (row, line) in s.lines().enumerate() {
-            for (col, byte) in line.bytes().enumerate() {
-                cells[row][col] = Cell::from_byte(byte)?;
-            }
+        let mut cells = s
+            .lines()
+            .map(|line| {
+                line.char_indices()
+                    .map(|(i, c)| Cell::from_char(c).map_err(|err| err.at(line, i).within(s, line)))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // Pad short lines so every row has the same width
+        let rows = cells.len();
+        let cols = cells.iter().map(Vec::len).max().unwrap_or(0);
+        for row in &mut cells {
+            row.resize(cols, Cell::Empty);
         }
 
-        Ok(Grid { cells, splits: 0 })
+        Ok(Grid {
+            cells,
+            rows,
+            cols,
+            splits: 0,
+        })
     }
 }
 
-impl<const R: usize, const C: usize> Display for Grid<R, C> {
+/// Beams show how many timelines they carry, or `|` once that's more than one digit.
+impl Display for Grid {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for row in 0..R {
-            for col in 0..C {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
                 let symbol = match self.cells[row][col] {
                     Cell::Source => 'S',
                     Cell::Splitter => '^',
-                    Cell::Beam(t) => (b'0' + t as u8) as char,
+                    Cell::Beam(t) => char::from_digit(t.min(10) as u32, 10).unwrap_or('|'),
                     Cell::Empty => '.',
                 };
                 write!(f, "{}", symbol)?;
@@ -81,84 +114,110 @@ impl<const R: usize, const C: usize> Display for Grid<R, C> {
     }
 }
 
-impl<const R: usize, const C: usize> Grid<R, C> {
-    fn emit_beam(&mut self) {
-        for row in 1..R {
-            for col in 0..C {
+impl Grid {
+    fn emit_beam(&mut self) -> Result<()> {
+        for row in 1..self.rows {
+            for col in 0..self.cols {
                 if matches!(self.cells[row][col], Cell::Beam(_)) {
                     // already filled by a splitter to the left
                     continue;
                 }
-                if matches!(self.cells[row - 1][col], Cell::Source | Cell::Beam(_)) {
-                    let timelines = match self.cells[row - 1][col] {
-                        Cell::Source => 1,
+                let timelines = self.cells[row - 1][col].timelines();
+                if timelines == 0 {
+                    continue;
+                }
+                if self.cells[row][col] != Cell::Splitter {
+                    self.cells[row][col] = Cell::Beam(timelines);
+                    continue;
+                }
+                if col == 0 || col + 1 == self.cols {
+                    bail!("Splitter at row {}, column {} is on the edge", row + 1, col + 1);
+                }
+                // assumption: splitter always has empty cells on left and right
+                for side in [col - 1, col + 1] {
+                    let existing = match self.cells[row][side] {
                         Cell::Beam(t) => t,
-                        _ => unreachable!(),
+                        _ => self.cells[row - 1][side].timelines(),
                     };
-                    if self.cells[row][col] == Cell::Splitter {
-                        // assumption: splitter always has empty cells on left and right
-                        let left = self.cells[row][col - 1];
-                        let left_timelines = match self.cells[row - 1][col - 1] {
-                            Cell::Source => 1,
-                            Cell::Beam(t) => t,
-                            _ => 0,
-                        };
-                        if let Cell::Beam(left_t) = left {
-                            self.cells[row][col - 1] = Cell::Beam(timelines + left_t);
-                        } else {
-                            self.cells[row][col - 1] = Cell::Beam(timelines + left_timelines);
-                        }
-                        let right = self.cells[row][col + 1];
-                        let right_timelines = match self.cells[row - 1][col + 1] {
-                            Cell::Source => 1,
-                            Cell::Beam(t) => t,
-                            _ => 0,
-                        };
-                        if let Cell::Beam(right_t) = right {
-                            self.cells[row][col + 1] = Cell::Beam(timelines + right_t);
-                        } else {
-                            self.cells[row][col + 1] = Cell::Beam(timelines + right_timelines);
-                        }
-                        self.splits += 1;
-                    } else {
-                        self.cells[row][col] = Cell::Beam(timelines);
-                    }
+                    let total = existing
+                        .checked_add(timelines)
+                        .ok_or_eyre("timeline count overflowed")?;
+                    self.cells[row][side] = Cell::Beam(total);
                 }
+                self.splits += 1;
             }
             debug!("After row {}:\n{}", row, self);
         }
+        Ok(())
     }
 }
 
-fn solve_part1<const R: usize, const C: usize>(input: &str) -> Result<usize> {
-    let mut grid = Grid::<R, C>::from_str(input)?;
-    grid.emit_beam();
+fn solve_part1(grid: &Grid) -> Result<usize> {
+    let mut grid = grid.clone();
+    grid.emit_beam()?;
     Ok(grid.splits)
 }
 
-#[instrument(skip(input))]
-pub fn part1(input: &str) -> Result<usize> {
-    solve_part1::<142, 141>(input)
-}
-
-fn solve_part2<const R: usize, const C: usize>(input: &str) -> Result<usize> {
-    let mut grid = Grid::<R, C>::from_str(input)?;
-    grid.emit_beam();
-    Ok(grid.cells[R - 1]
+fn solve_part2(grid: &Grid) -> Result<usize> {
+    let mut grid = grid.clone();
+    grid.emit_beam()?;
+    grid.cells
+        .last()
         .into_iter()
-        .map(|c| {
-            if let Cell::Beam(timelines) = c {
-                timelines
-            } else {
-                0
-            }
+        .flatten()
+        .try_fold(0usize, |total, c| match c {
+            &Cell::Beam(timelines) => total.checked_add(timelines),
+            _ => Some(total),
         })
-        .sum::<usize>())
+        .ok_or_eyre("timeline count overflowed")
 }
 
-#[instrument(skip(input))]
-pub fn part2(input: &str) -> Result<usize> {
-    solve_part2::<142, 141>(input)
+impl Solution for Day {
+    type Parsed = Grid;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    #[instrument(skip(input))]
+    fn parse(input: &str) -> Result<Grid> {
+        Ok(input.parse()?)
+    }
+
+    /// Besides being a rectangular grid of valid cells, the first row must hold the only source
+    /// and every splitter needs an empty cell on either side.
+    fn validate(input: &str) -> Vec<ParseError> {
+        let mut errors = invalid_chars(input, "Invalid cell", |c| Cell::from_char(c).is_ok());
+        errors.extend(ragged_rows(input));
+        for (row, line) in input.lines().enumerate() {
+            let bytes = line.as_bytes();
+            for (col, &byte) in bytes.iter().enumerate() {
+                let error = match byte {
+                    b'S' if row > 0 => "Source is not in the first row",
+                    b'S' if line[col + 1..].contains('S') => "First row has more than one source",
+                    b'^' if col == 0 || col + 1 == line.len() => "Splitter is on the edge",
+                    b'^' if bytes[col - 1] != b'.' || bytes[col + 1] != b'.' => {
+                        "Splitter is not surrounded by empty cells"
+                    }
+                    _ => continue,
+                };
+                let err = ParseError::new(error, char::from(byte)).at(line, col);
+                errors.push(err.within(input, line));
+            }
+        }
+        if !input.lines().next().is_some_and(|line| line.contains('S')) {
+            errors.push(ParseError::new("First row has no source", ""));
+        }
+        errors
+    }
+
+    #[instrument(skip(grid))]
+    fn part1(grid: &Grid) -> Result<usize> {
+        solve_part1(grid)
+    }
+
+    #[instrument(skip(grid))]
+    fn part2(grid: &Grid) -> Result<usize> {
+        solve_part2(grid)
+    }
 }
 
 #[cfg(test)]
@@ -168,13 +227,45 @@ mod tests {
 
     const TEST_INPUT1: &str = include_str!("input/test1.txt");
 
+    #[test]
+    fn test_display_many_timelines() {
+        let mut grid = ".S.\n...".parse::<Grid>().unwrap();
+        grid.cells[1] = vec![Cell::Beam(9), Cell::Beam(1000), Cell::Beam(usize::MAX)];
+        assert_eq!(grid.to_string(), ".S.\n9||\n");
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(solve_part1::<16, 15>(TEST_INPUT1).unwrap(), 21);
+        let grid = TEST_INPUT1.parse::<Grid>().unwrap();
+        assert_eq!(solve_part1(&grid).unwrap(), 21);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(solve_part2::<16, 15>(TEST_INPUT1).unwrap(), 40);
+        let grid = TEST_INPUT1.parse::<Grid>().unwrap();
+        assert_eq!(solve_part2(&grid).unwrap(), 40);
+    }
+
+    #[test]
+    fn test_invalid_cell() {
+        let err = "..S\n.é^".parse::<Grid>().err().unwrap();
+        assert!(err.to_string().starts_with("Invalid cell: `é`\n --> 2:2"));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(Day::validate(TEST_INPUT1).is_empty());
+        let errors = Day::validate("..S.S\n^.^^.\n..S..");
+        let messages: Vec<_> = errors.iter().map(ParseError::message).collect();
+        assert_eq!(
+            messages,
+            [
+                "First row has more than one source",
+                "Splitter is on the edge",
+                "Splitter is not surrounded by empty cells",
+                "Splitter is not surrounded by empty cells",
+                "Source is not in the first row",
+            ]
+        );
     }
 }
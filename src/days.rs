@@ -1,17 +1,16 @@
-// Single source of truth for all implemented days
-// Add new days here and they'll automatically be available in both the runner and benchmarks
+// Single source of truth for all implemented days, generated by build.rs from every src/dayNN/
+// directory with a mod.rs. New days automatically show up in both the runner and benchmarks.
+//
+// Expands to:
+//
+// macro_rules! all_days {
+//     ($macro_name:path) => {
+//         $macro_name! {
+//             1 => day01,
+//             2 => day02,
+//             ...
+//         }
+//     };
+// }
 
-#[macro_export]
-macro_rules! all_days {
-    ($macro_name:path) => {
-        $macro_name! {
-            1 => day01,
-            2 => day02,
-            3 => day03,
-            4 => day04,
-            5 => day05,
-            6 => day06,
-            7 => day07,
-        }
-    };
-}
+include!(concat!(env!("OUT_DIR"), "/all_days.rs"));
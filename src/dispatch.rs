@@ -0,0 +1,79 @@
+use color_eyre::{Result, eyre::bail};
+
+use crate::{Answer, Solution, parse::ParseError};
+
+macro_rules! dispatch {
+    ($($day_num:literal => $day_mod:ident),* $(,)?) => {
+        const DAYS: &[u8] = &[$($day_num),*];
+
+        /// Solves `part` of `day` for `input`.
+        pub fn solve(day: u8, part: u8, input: &str) -> Result<Answer> {
+            match (day, part) {
+                $(
+                    ($day_num, 1) => crate::$day_mod::Day::solve_part1(input).map(Into::into),
+                    ($day_num, 2) => crate::$day_mod::Day::solve_part2(input).map(Into::into),
+                )*
+                (_, 1 | 2) => bail!("Day {} is not yet implemented", day),
+                _ => bail!("Part {} is invalid. Must be 1 or 2.", part),
+            }
+        }
+
+        /// Checks `input` against the assumptions `day`'s solution makes, returning every
+        /// violation.
+        pub fn validate(day: u8, input: &str) -> Result<Vec<ParseError>> {
+            match day {
+                $(
+                    $day_num => Ok(crate::$day_mod::Day::validate(input)),
+                )*
+                _ => bail!("Day {} is not yet implemented", day),
+            }
+        }
+    };
+}
+
+all_days!(dispatch);
+
+/// Every day with a solution, in order.
+pub fn available_days() -> impl Iterator<Item = u8> {
+    DAYS.iter().copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_solve() {
+        let input = include_str!("day01/input/test1.txt");
+        assert_eq!(solve(1, 1, input).unwrap(), Answer::from(3));
+        assert_eq!(solve(1, 2, input).unwrap(), Answer::from(6));
+    }
+
+    #[test]
+    fn test_solve_invalid() {
+        let err = solve(99, 1, "").unwrap_err();
+        assert!(err.to_string().contains("Day 99 is not yet implemented"));
+        let err = solve(1, 3, "").unwrap_err();
+        assert!(err.to_string().contains("Part 3 is invalid"));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(
+            validate(1, include_str!("day01/input/test1.txt"))
+                .unwrap()
+                .is_empty()
+        );
+        let errors = validate(1, "L68\nU30\nR-5").unwrap();
+        let positions: Vec<_> = errors.iter().map(ParseError::position).collect();
+        assert_eq!(positions, [Some((2, 1)), Some((3, 2))]);
+    }
+
+    #[test]
+    fn test_available_days() {
+        let days: Vec<u8> = available_days().collect();
+        assert_eq!(days.first(), Some(&1));
+        assert!(days.is_sorted());
+    }
+}
@@ -0,0 +1,185 @@
+use std::path::PathBuf;
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail, eyre},
+};
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::{Answer, Solution, Variant, input};
+
+pub const EXAMPLES_FILE: &str = "examples.toml";
+
+/// Path of a day's examples manifest, e.g. `src/day01/examples.toml`.
+pub fn manifest_path(day: u8) -> PathBuf {
+    input::day_dir(day).join(EXAMPLES_FILE)
+}
+
+/// An example input with the answers the puzzle description gives for it. The input is either a
+/// `file` in the day's input directory or an inline `input` string. Answers too big for a TOML
+/// integer can be written as strings.
+///
+/// ```toml
+/// [[example]]
+/// file = "test1.txt"
+/// part1 = 3
+/// part2 = 6
+///
+/// [[example]]
+/// name = "triple overlap"
+/// input = "3-4\n2-5\n1-6"
+/// part2 = 6
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Example {
+    pub name: Option<String>,
+    pub file: Option<String>,
+    pub input: Option<String>,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    #[serde(default, rename = "example")]
+    examples: Vec<Example>,
+}
+
+impl Example {
+    /// A name for the example in test output: its `name`, else its `file`, else its position.
+    pub fn label(&self, index: usize) -> String {
+        self.name
+            .clone()
+            .or_else(|| self.file.clone())
+            .unwrap_or_else(|| format!("example {}", index + 1))
+    }
+
+    /// Reads the example's input, from its file or inline.
+    pub fn input(&self, day: u8) -> Result<String> {
+        match (&self.file, &self.input) {
+            (Some(file), None) => {
+                let path = input::input_dir(day).join(file);
+                std::fs::read_to_string(&path)
+                    .wrap_err_with(|| format!("reading example {}", path.display()))
+            }
+            (None, Some(input)) => Ok(input.clone()),
+            _ => bail!("An example needs exactly one of `file` or `input`"),
+        }
+    }
+
+    /// The expected answer for `part`, if the example has one.
+    pub fn expected(&self, part: u8) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Reads the examples manifest for `day`.
+pub fn load(day: u8) -> Result<Vec<Example>> {
+    let path = manifest_path(day);
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|err| eyre!(err).wrap_err(format!("reading {}", path.display())))?;
+    let manifest: Manifest =
+        toml::from_str(&contents).wrap_err_with(|| format!("parsing {}", path.display()))?;
+    Ok(manifest.examples)
+}
+
+fn check_part<T: Into<Answer>>(result: Result<T>, expected: &Answer) -> Option<String> {
+    match result.map(Into::into) {
+        Ok(answer) if answer == *expected => None,
+        Ok(answer) => Some(format!("expected {}, got {}", expected, answer)),
+        Err(err) => Some(format!("expected {}, got error: {}", expected, err)),
+    }
+}
+
+/// Each variant's name and its answer for `parsed`.
+fn variant_answers<P, T: Into<Answer>>(
+    parsed: &P,
+    variants: Vec<Variant<P, T>>,
+) -> Vec<(&'static str, Result<Answer>)> {
+    variants
+        .into_iter()
+        .map(|variant| (variant.name, (variant.solve)(parsed).map(Into::into)))
+        .collect()
+}
+
+/// Runs every example for `day` through `S`, including each part's named variants, and fails with
+/// a list of every mismatch.
+pub fn check<S: Solution>(day: u8) -> Result<()> {
+    let mut failures = Vec::new();
+    for (index, example) in load(day)?.iter().enumerate() {
+        let label = example.label(index);
+        let input = example.input(day)?;
+        let parsed = S::parse(&input);
+        for part in [1, 2] {
+            let Some(expected) = example.expected(part) else {
+                continue;
+            };
+            debug!(day, part, example = %label, "checking example");
+            let failure = match part {
+                1 => check_part(S::solve_part1(&input), expected),
+                _ => check_part(S::solve_part2(&input), expected),
+            };
+            if let Some(failure) = failure {
+                failures.push(format!("{} part {}: {}", label, part, failure));
+            }
+            let Ok(parsed) = &parsed else { continue };
+            let variants = match part {
+                1 => variant_answers(parsed, S::part1_variants()),
+                _ => variant_answers(parsed, S::part2_variants()),
+            };
+            for (name, result) in variants {
+                if let Some(failure) = check_part(result, expected) {
+                    failures.push(format!("{} part {}@{}: {}", label, part, name, failure));
+                }
+            }
+        }
+    }
+    if !failures.is_empty() {
+        bail!(
+            "Day {} failed {} example(s):\n{}",
+            day,
+            failures.len(),
+            failures.join("\n")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+[[example]]
+file = "test1.txt"
+part1 = 3
+part2 = "98765432109876543210"
+
+[[example]]
+input = "3-4"
+part2 = 2
+"#,
+        )
+        .unwrap();
+        let [file, inline] = &manifest.examples[..] else {
+            panic!("expected two examples");
+        };
+        assert_eq!(file.label(0), "test1.txt");
+        assert_eq!(file.expected(1), Some(&Answer::from(3)));
+        assert_eq!(file.expected(2), Some(&Answer::Big(98765432109876543210)));
+        assert_eq!(inline.label(1), "example 2");
+        assert_eq!(inline.input(5).unwrap(), "3-4");
+        assert_eq!(inline.expected(1), None);
+    }
+}
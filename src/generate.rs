@@ -0,0 +1,209 @@
+//! Seeded generators for synthetic puzzle inputs.
+//!
+//! Every generator produces an input with the same structure as the real one, so it passes
+//! [`crate::validate`], at a size given by the caller. The same day, seed and size always give the
+//! same input.
+
+use color_eyre::{Result, eyre::bail};
+use itertools::Itertools;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+/// Joltage digits per bank, as in the real day 3 inputs.
+const BANK_WIDTH: usize = 100;
+/// Number rows per problem in the day 6 worksheet.
+const WORKSHEET_ROWS: usize = 4;
+/// Day 7 stops adding splitters once part 2 would count this many timelines.
+const MAX_TIMELINES: u64 = 1 << 48;
+
+/// Generates an input for `day` from `seed`. What `size` counts depends on the day:
+///
+/// | Day | `size` |
+/// |-----|--------|
+/// | 1   | rotations |
+/// | 2   | ID ranges |
+/// | 3   | banks of 100 batteries |
+/// | 4   | rows and columns of the grid |
+/// | 5   | fresh ranges, and as many ingredient IDs |
+/// | 6   | problems |
+/// | 7   | rows and columns of the manifold |
+pub fn generate(day: u8, seed: u64, size: usize) -> Result<String> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let rng = &mut rng;
+    Ok(match day {
+        1 => day01(rng, size),
+        2 => day02(rng, size),
+        3 => day03(rng, size),
+        4 => day04(rng, size),
+        5 => day05(rng, size),
+        6 => day06(rng, size),
+        7 => day07(rng, size),
+        _ => bail!("No input generator for day {}", day),
+    })
+}
+
+/// Dial rotations like `L68`.
+pub fn day01(rng: &mut impl Rng, rotations: usize) -> String {
+    (0..rotations)
+        .map(|_| {
+            let direction = if rng.random_bool(0.5) { 'L' } else { 'R' };
+            format!("{}{}\n", direction, rng.random_range(1..1000))
+        })
+        .collect()
+}
+
+/// Comma-separated product ID ranges of up to 10 digits.
+pub fn day02(rng: &mut impl Rng, ranges: usize) -> String {
+    let ranges = (0..ranges)
+        .map(|_| {
+            let digits = rng.random_range(1..10);
+            let start: i64 = rng.random_range(1..10_i64.pow(digits));
+            let end = start + rng.random_range(0..=start / 10 + 100);
+            format!("{}-{}", start, end)
+        })
+        .join(",");
+    ranges + "\n"
+}
+
+/// Banks of joltage digits from 1 to 9.
+pub fn day03(rng: &mut impl Rng, banks: usize) -> String {
+    (0..banks)
+        .map(|_| {
+            let mut bank: String = (0..BANK_WIDTH)
+                .map(|_| char::from(b'0' + rng.random_range(1..=9)))
+                .collect();
+            bank.push('\n');
+            bank
+        })
+        .collect()
+}
+
+/// A square grid of empty cells and paper rolls.
+pub fn day04(rng: &mut impl Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut row: String = (0..size)
+                .map(|_| if rng.random_bool(0.6) { '@' } else { '.' })
+                .collect();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+/// Fresh ID ranges, a blank line, then as many ingredient IDs. About half the IDs fall inside a
+/// range.
+pub fn day05(rng: &mut impl Rng, ranges: usize) -> String {
+    const MAX_ID: i64 = 1_000_000_000_000_000;
+    let fresh = (0..ranges)
+        .map(|_| {
+            let start = rng.random_range(1..MAX_ID);
+            (start, start + rng.random_range(0..MAX_ID / 1000))
+        })
+        .collect::<Vec<_>>();
+    let mut input = fresh
+        .iter()
+        .map(|(start, end)| format!("{}-{}\n", start, end))
+        .collect::<String>();
+    input.push('\n');
+    for _ in 0..ranges {
+        let id = match fresh.get(rng.random_range(0..fresh.len() * 2)) {
+            Some(&(start, end)) => rng.random_range(start..=end),
+            None => rng.random_range(1..MAX_ID),
+        };
+        input += &format!("{}\n", id);
+    }
+    input
+}
+
+/// A worksheet of problems side by side. Each problem is as wide as its longest number, with its
+/// numbers all aligned left or all aligned right, and the operators padded to the same width.
+pub fn day06(rng: &mut impl Rng, problems: usize) -> String {
+    let mut rows = vec![Vec::new(); WORKSHEET_ROWS + 1];
+    for _ in 0..problems {
+        let numbers = (0..WORKSHEET_ROWS)
+            .map(|_| rng.random_range(1..1000).to_string())
+            .collect::<Vec<_>>();
+        let width = numbers.iter().map(String::len).max().unwrap_or(1);
+        let left = rng.random_bool(0.5);
+        for (row, number) in rows.iter_mut().zip(&numbers) {
+            row.push(match left {
+                true => format!("{:<width$}", number),
+                false => format!("{:>width$}", number),
+            });
+        }
+        let operation = if rng.random_bool(0.5) { "+" } else { "*" };
+        rows[WORKSHEET_ROWS].push(format!("{:<width$}", operation));
+    }
+    rows.iter().map(|row| row.join(" ") + "\n").collect()
+}
+
+/// A square manifold with the source in the middle of the top row and splitters on every other
+/// row after it. Splitters never touch each other or the edges, and are only added while part 2's
+/// timeline count stays below [`MAX_TIMELINES`].
+pub fn day07(rng: &mut impl Rng, size: usize) -> String {
+    let size = size.max(3);
+    let mut grid = vec![vec![b'.'; size]; size];
+    let source = size / 2;
+    grid[0][source] = b'S';
+    let mut timelines = vec![0u64; size];
+    timelines[source] = 1;
+    let mut total = 1;
+    for row in (2..size).step_by(2) {
+        // Splitters on one parity of columns are never next to each other
+        for col in (1 + row / 2 % 2..size - 1).step_by(2) {
+            let beams = timelines[col];
+            if total + beams > MAX_TIMELINES || !rng.random_bool(0.5) {
+                continue;
+            }
+            grid[row][col] = b'^';
+            timelines[col - 1] += beams;
+            timelines[col + 1] += beams;
+            timelines[col] = 0;
+            total += beams;
+        }
+    }
+    grid.into_iter()
+        .map(|mut row| {
+            row.push(b'\n');
+            String::from_utf8(row).expect("grid is ASCII")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_generated_inputs_are_valid() {
+        for day in crate::available_days() {
+            for seed in 0..3 {
+                let input = generate(day, seed, 50).unwrap();
+                let errors = crate::validate(day, &input).unwrap();
+                assert!(
+                    errors.is_empty(),
+                    "day {} seed {}: {}",
+                    day,
+                    seed,
+                    errors[0]
+                );
+                crate::solve(day, 1, &input).unwrap();
+                crate::solve(day, 2, &input).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_seeded() {
+        assert_eq!(generate(4, 7, 20).unwrap(), generate(4, 7, 20).unwrap());
+        assert_ne!(generate(4, 7, 20).unwrap(), generate(4, 8, 20).unwrap());
+        assert_eq!(generate(1, 7, 20).unwrap().lines().count(), 20);
+    }
+
+    #[test]
+    fn test_unknown_day() {
+        let err = generate(99, 0, 10).unwrap_err();
+        assert!(err.to_string().contains("No input generator for day 99"));
+    }
+}
@@ -0,0 +1,165 @@
+use std::{
+    borrow::Cow,
+    convert::Infallible,
+    fmt::{Display, Formatter},
+    io::{ErrorKind, Read},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, eyre},
+};
+use tracing::debug;
+
+/// Where to read a day's puzzle input from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The conventional location: `src/dayNN/input/input.txt`.
+    #[default]
+    Default,
+    /// Standard input.
+    Stdin,
+    /// An explicit file path.
+    File(PathBuf),
+}
+
+impl FromStr for InputSource {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "-" => Ok(InputSource::Stdin),
+            path => Ok(InputSource::File(PathBuf::from(path))),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Default => write!(f, "default"),
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// A day's source directory, e.g. `src/day01`.
+pub fn day_dir(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join(format!("day{:02}", day))
+}
+
+/// Directory holding a day's input files, e.g. `src/day01/input`.
+pub fn input_dir(day: u8) -> PathBuf {
+    day_dir(day).join("input")
+}
+
+/// Path of a day's real puzzle input, e.g. `src/day01/input/input.txt`.
+pub fn default_path(day: u8) -> PathBuf {
+    input_dir(day).join("input.txt")
+}
+
+macro_rules! puzzle_inputs {
+    ($($day_num:literal => $day_mod:ident),* $(,)?) => {
+        /// Puzzle input for `day`, embedded into the binary at compile time.
+        #[cfg(feature = "embed-inputs")]
+        pub fn puzzle_input(day: u8) -> Result<Cow<'static, str>> {
+            match day {
+                $(
+                    $day_num => Ok(Cow::Borrowed(include_str!(concat!(
+                        env!("CARGO_MANIFEST_DIR"),
+                        "/src/",
+                        stringify!($day_mod),
+                        "/input/input.txt"
+                    )))),
+                )*
+                _ => Err(eyre!("Day {} is not yet implemented", day)),
+            }
+        }
+
+        /// Puzzle input for `day`, read from `src/dayNN/input/input.txt` at runtime.
+        #[cfg(not(feature = "embed-inputs"))]
+        pub fn puzzle_input(day: u8) -> Result<Cow<'static, str>> {
+            match day {
+                $(
+                    $day_num => read_file(day, &default_path(day)).map(Cow::Owned),
+                )*
+                _ => Err(eyre!("Day {} is not yet implemented", day)),
+            }
+        }
+    };
+}
+
+all_days!(puzzle_inputs);
+
+/// Reads the puzzle input for `day` from `source`.
+pub fn load(day: u8, source: &InputSource) -> Result<Cow<'static, str>> {
+    debug!(day, source = %source, "loading input");
+    match source {
+        InputSource::Default => puzzle_input(day),
+        InputSource::File(path) => read_file(day, path).map(Cow::Owned),
+        InputSource::Stdin => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .wrap_err("reading input from stdin")?;
+            Ok(Cow::Owned(input))
+        }
+    }
+}
+
+fn read_file(day: u8, path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|err| match err.kind() {
+        ErrorKind::NotFound => eyre!(
+            "Input for day {} not found at {}. Save your puzzle input from https://adventofcode.com/2025/day/{}/input there, or pass --input <PATH>.",
+            day,
+            path.display(),
+            day
+        ),
+        _ => eyre!(err).wrap_err(format!("reading input file {}", path.display())),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_parse_source() {
+        assert_eq!("-".parse::<InputSource>().unwrap(), InputSource::Stdin);
+        assert_eq!(
+            "foo.txt".parse::<InputSource>().unwrap(),
+            InputSource::File(PathBuf::from("foo.txt"))
+        );
+    }
+
+    #[test]
+    fn test_default_path() {
+        assert!(default_path(1).ends_with("src/day01/input/input.txt"));
+    }
+
+    #[test]
+    fn test_load_file() {
+        let path = input_dir(1).join("test1.txt");
+        let input = load(1, &InputSource::File(path)).unwrap();
+        assert!(input.starts_with("L68"));
+    }
+
+    #[test]
+    fn test_unimplemented_day() {
+        let err = puzzle_input(99).unwrap_err();
+        assert!(err.to_string().contains("Day 99 is not yet implemented"));
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let path = input_dir(1).join("does-not-exist.txt");
+        let err = load(1, &InputSource::File(path)).unwrap_err();
+        assert!(err.to_string().contains("Input for day 1 not found"));
+    }
+}
@@ -1,8 +1,17 @@
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
+// Declares a `pub mod dayNN` for every src/dayNN/ directory, generated by build.rs
+include!(concat!(env!("OUT_DIR"), "/day_modules.rs"));
+
+#[macro_use]
 pub mod days;
+pub mod answer;
+pub mod dispatch;
+pub mod examples;
+#[cfg(feature = "generate")]
+pub mod generate;
+pub mod input;
+pub mod parse;
+pub mod solution;
+
+pub use answer::Answer;
+pub use dispatch::{available_days, solve, validate};
+pub use solution::{NotImplemented, Solution, Variant};
@@ -0,0 +1,100 @@
+use color_eyre::Result;
+
+use crate::{answers::Answers, report, timings::Timings};
+
+const HEADERS: [&str; 6] = ["Day", "Input", "Examples", "Answers", "Part 1", "Part 2"];
+
+/// What's known about one day, as shown by the `list` subcommand.
+#[derive(Debug, Clone)]
+pub struct DayStatus {
+    pub day: u8,
+    pub has_input: bool,
+    pub examples: usize,
+    /// Parts with a known answer recorded.
+    pub answered: Vec<u8>,
+    pub timings: [Option<std::time::Duration>; 2],
+}
+
+impl DayStatus {
+    /// Gathers the status of `day` from the files on disk and the given manifests.
+    pub fn load(day: u8, answers: &Answers, timings: &Timings) -> Result<Self> {
+        let examples = if aoc::examples::manifest_path(day).exists() {
+            aoc::examples::load(day)?.len()
+        } else {
+            0
+        };
+        Ok(DayStatus {
+            day,
+            has_input: aoc::input::default_path(day).exists(),
+            examples,
+            answered: [1, 2]
+                .into_iter()
+                .filter(|&part| answers.get(day, part).is_some())
+                .collect(),
+            timings: [timings.get(day, 1), timings.get(day, 2)],
+        })
+    }
+}
+
+/// Renders a table with a row per day.
+pub fn status_table(statuses: &[DayStatus]) -> String {
+    let rows: Vec<[String; 6]> = statuses
+        .iter()
+        .map(|status| {
+            [
+                format!("{:02}", status.day),
+                if status.has_input { "✔" } else { "missing" }.to_string(),
+                status.examples.to_string(),
+                match &status.answered[..] {
+                    [] => "-".to_string(),
+                    parts => parts
+                        .iter()
+                        .map(|part| format!("part {}", part))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                },
+                report::format_duration(status.timings[0]),
+                report::format_duration(status.timings[1]),
+            ]
+        })
+        .collect();
+    report::table(HEADERS, &rows, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use test_log::test;
+
+    #[test]
+    fn test_status_table() {
+        let statuses = vec![
+            DayStatus {
+                day: 1,
+                has_input: true,
+                examples: 2,
+                answered: vec![1, 2],
+                timings: [Some(Duration::from_micros(20)), None],
+            },
+            DayStatus {
+                day: 2,
+                has_input: false,
+                examples: 0,
+                answered: vec![],
+                timings: [None, None],
+            },
+        ];
+        let table = status_table(&statuses);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[2],
+            "| 01  |       ✔ |        2 | part 1, part 2 | 20.00µs |      - |"
+        );
+        assert_eq!(
+            lines[3],
+            "| 02  | missing |        0 |              - |       - |      - |"
+        );
+    }
+}
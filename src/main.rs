@@ -1,19 +1,37 @@
-mod days;
+mod answers;
+mod client;
+mod list;
+mod manifest;
+mod rejected;
+mod report;
 mod runner;
+mod scaffold;
+mod submit;
+#[cfg(test)]
+mod test_util;
+mod timings;
+mod watch;
 
+use std::path::PathBuf;
+
+use aoc::input::InputSource;
 use aoc::*;
-use clap::Parser;
+use clap::{CommandFactory, Parser, Subcommand, error::ErrorKind};
 use color_eyre::Result;
+use report::OutputFormat;
 use tracing::info;
 use tracing_error::ErrorLayer;
 use tracing_subscriber::EnvFilter;
-use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::{format::FmtSpan, writer::BoxMakeWriter};
 use tracing_subscriber::prelude::*;
 
 #[derive(Parser, Debug)]
 #[command(name = "Advent of Code 2025")]
 #[command(about = "Solutions for Advent of Code 2025", long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Day to run (1-25). If not specified, runs all days.
     #[arg(short, long)]
     day: Option<u8>,
@@ -21,6 +39,182 @@ struct Args {
     /// Part to run (1 or 2). If not specified, runs all parts.
     #[arg(short, long)]
     part: Option<u8>,
+
+    /// Input file to use instead of src/dayNN/input/input.txt. Use "-" to read from stdin. Requires --day.
+    #[arg(short, long)]
+    input: Option<InputSource>,
+
+    /// Run against the example in src/dayNN/input/testN.txt instead of the real input (1 if N is
+    /// omitted). Requires --day.
+    #[arg(short, long, value_name = "N", num_args = 0..=1, default_missing_value = "1", requires = "day", conflicts_with = "input")]
+    example: Option<u8>,
+
+    /// Manifest of known answers to check results against. Defaults to answers.toml.
+    #[arg(long)]
+    answers: Option<PathBuf>,
+
+    /// Print a table of parse and solve times for each day at the end.
+    #[arg(short, long)]
+    time: bool,
+
+    /// Run days and parts concurrently on the rayon thread pool. Answers are still reported in
+    /// day order, but timings include contention with the other jobs.
+    #[arg(long)]
+    parallel: bool,
+
+    /// Also run every named variant of each part (e.g. day 5 part 2@naive), failing if any
+    /// disagrees with the main solution.
+    #[arg(long)]
+    variants: bool,
+
+    /// Number of threads in the rayon pool. Defaults to one per CPU.
+    #[arg(long)]
+    threads: Option<usize>,
+
+    /// Output format for answers. The JSON formats write records to stdout and logs to stderr.
+    #[arg(short, long, value_enum, default_value_t)]
+    format: OutputFormat,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List every implemented day with its input, examples, recorded answers and last runtimes
+    List,
+    /// Create src/dayNN/ from the day template
+    New {
+        /// Day to create (1-25)
+        day: u8,
+    },
+    /// Download a day's puzzle input to src/dayNN/input/input.txt, unless it's already there
+    Fetch {
+        /// Day to download (1-25)
+        #[arg(short, long)]
+        day: u8,
+
+        /// Base URL of the Advent of Code site. Defaults to $AOC_BASE_URL or https://adventofcode.com.
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+    /// Re-run a day whenever its input files change
+    Watch {
+        /// Day to watch (1-25)
+        #[arg(short, long)]
+        day: u8,
+
+        /// Part to run (1 or 2). If not specified, runs both parts.
+        #[arg(short, long)]
+        part: Option<u8>,
+
+        /// Run against the example in src/dayNN/input/testN.txt instead of the real input (1 if N
+        /// is omitted).
+        #[arg(short, long, value_name = "N", num_args = 0..=1, default_missing_value = "1")]
+        example: Option<u8>,
+
+        /// Also watch the day's source files, rebuilding and restarting when they change.
+        #[arg(long)]
+        rebuild: bool,
+    },
+    /// Check a day's input against the assumptions its solution makes, reporting every violation
+    Validate {
+        /// Day to validate (1-25)
+        #[arg(short, long)]
+        day: u8,
+
+        /// Input file to check instead of src/dayNN/input/input.txt. Use "-" to read from stdin.
+        #[arg(short, long)]
+        input: Option<InputSource>,
+
+        /// Check the example in src/dayNN/input/testN.txt instead (1 if N is omitted).
+        #[arg(short, long, value_name = "N", num_args = 0..=1, default_missing_value = "1", conflicts_with = "input")]
+        example: Option<u8>,
+    },
+    /// Print a synthetic input for a day, generated from a seed
+    #[cfg(feature = "generate")]
+    Generate {
+        /// Day to generate an input for (1-25)
+        #[arg(short, long)]
+        day: u8,
+
+        /// Seed for the random generator. The same seed and size always give the same input.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// How big the input is, e.g. the number of lines or the grid width. See aoc::generate.
+        #[arg(long, default_value_t = 1000)]
+        size: usize,
+    },
+    /// Run a part against the real input and submit the answer, recording it if it's correct
+    Submit {
+        /// Day to submit (1-25)
+        #[arg(short, long)]
+        day: u8,
+
+        /// Part to submit (1 or 2)
+        #[arg(short, long)]
+        part: u8,
+
+        /// Base URL of the Advent of Code site. Defaults to $AOC_BASE_URL or https://adventofcode.com.
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+}
+
+fn client(base_url: Option<String>) -> Result<client::Client> {
+    match base_url {
+        Some(base_url) => Ok(client::Client::new(base_url, client::session_token()?)),
+        None => client::Client::from_env(),
+    }
+}
+
+/// The example in `src/dayNN/input/testN.txt`, if it exists.
+fn example_input(day: u8, example: u8) -> Result<InputSource> {
+    let path = input::input_dir(day).join(format!("test{}.txt", example));
+    if !path.exists() {
+        color_eyre::eyre::bail!(
+            "Example {} for day {} not found at {}",
+            example,
+            day,
+            path.display()
+        );
+    }
+    Ok(InputSource::File(path))
+}
+
+/// Known and rejected answers for `input`. They only apply to the real puzzle inputs, so other
+/// inputs get empty manifests.
+fn known_answers(
+    input: &InputSource,
+    answers_path: Option<PathBuf>,
+) -> Result<(answers::Answers, rejected::Rejected)> {
+    if *input != InputSource::Default {
+        return Ok(Default::default());
+    }
+    let answers_path = answers_path.unwrap_or_else(answers::default_path);
+    Ok((
+        answers::Answers::load(&answers_path)?,
+        rejected::Rejected::load(&rejected::path_for(&answers_path))?,
+    ))
+}
+
+/// The answers `examples.toml` gives for the example in `src/dayNN/input/testN.txt`, to check a
+/// run against it.
+fn example_answers(day: u8, example: u8) -> Result<answers::Answers> {
+    let mut answers = answers::Answers::default();
+    if !aoc::examples::manifest_path(day).exists() {
+        return Ok(answers);
+    }
+    let file = format!("test{}.txt", example);
+    for example in aoc::examples::load(day)? {
+        if example.file.as_deref() != Some(file.as_str()) {
+            continue;
+        }
+        for part in [1, 2] {
+            if let Some(answer) = example.expected(part) {
+                answers.record(day, part, answer.clone());
+            }
+        }
+    }
+    Ok(answers)
 }
 
 all_days!(runner::days);
@@ -28,22 +222,193 @@ all_days!(runner::days);
 fn main() -> Result<()> {
     color_eyre::install()?;
 
+    let args = Args::parse();
+    if args.time && args.format != OutputFormat::Text {
+        Args::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--time requires --format text; the JSON records already include the durations",
+            )
+            .exit();
+    }
+
+    // The JSON formats replace the answer log lines, so only log warnings unless asked otherwise
+    let (default_filter, writer) = match args.format {
+        OutputFormat::Text => ("info", BoxMakeWriter::new(std::io::stdout)),
+        OutputFormat::Json | OutputFormat::Ndjson => ("warn", BoxMakeWriter::new(std::io::stderr)),
+    };
+    // Generated inputs are written to stdout, so keep the logs out of them
+    #[cfg(feature = "generate")]
+    let (default_filter, writer) = match args.command {
+        Some(Command::Generate { .. }) => ("warn", BoxMakeWriter::new(std::io::stderr)),
+        _ => (default_filter, writer),
+    };
     tracing_subscriber::registry()
-        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
+        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| default_filter.into()))
         .with(ErrorLayer::default())
         .with(
             tracing_subscriber::fmt::layer()
+                .with_writer(writer)
                 .with_line_number(true)
                 .with_span_events(FmtSpan::CLOSE),
         )
         .init();
 
-    let args = Args::parse();
+    if let Some(threads) = args.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()?;
+    }
 
     info!("Advent of Code 2025");
     let _span = tracing::info_span!("aoc").entered();
 
-    run_days(args.day, args.part)?;
+    if let Some(command) = args.command {
+        return match command {
+            Command::List => {
+                let answers =
+                    answers::Answers::load(&args.answers.unwrap_or_else(answers::default_path))?;
+                let timings = timings::Timings::load(&timings::default_path())?;
+                let statuses = available_days()
+                    .map(|day| list::DayStatus::load(day, &answers, &timings))
+                    .collect::<Result<Vec<_>>>()?;
+                print!("{}", list::status_table(&statuses));
+                Ok(())
+            }
+            Command::New { day } => scaffold::new_day(day),
+            Command::Fetch { day, base_url } => {
+                let path = input::default_path(day);
+                if path.exists() {
+                    info!("Day {} input already downloaded to {}", day, path.display());
+                    return Ok(());
+                }
+                client::fetch_input(&client(base_url)?, day, &path)
+            }
+            Command::Watch {
+                day,
+                part,
+                example,
+                rebuild,
+            } => {
+                let input = match example {
+                    Some(example) => example_input(day, example)?,
+                    None => InputSource::Default,
+                };
+                let (answers, rejected) = known_answers(&input, args.answers)?;
+                let mut inputs = vec![input::input_dir(day)];
+                if example.is_some() {
+                    inputs.push(aoc::examples::manifest_path(day));
+                }
+                let sources = rebuild.then(|| vec![input::day_dir(day)]);
+                watch::watch(inputs, sources, || {
+                    // Pick up edits to the example's expected answers too
+                    let answers = match example {
+                        Some(example) => example_answers(day, example)?,
+                        None => answers.clone(),
+                    };
+                    let reports = run_days(
+                        Some(day),
+                        part,
+                        &input,
+                        &answers,
+                        &rejected,
+                        runner::RunOptions::default(),
+                    )?;
+                    print!("{}", report::timing_table(&reports));
+                    Ok(())
+                })
+            }
+            Command::Validate {
+                day,
+                input,
+                example,
+            } => {
+                let source = match example {
+                    Some(example) => example_input(day, example)?,
+                    None => input.unwrap_or_default(),
+                };
+                let errors = validate(day, &input::load(day, &source)?)?;
+                if errors.is_empty() {
+                    info!("Day {} input ({}) is valid", day, source);
+                    return Ok(());
+                }
+                for err in &errors {
+                    eprintln!("{}\n", err);
+                }
+                color_eyre::eyre::bail!(
+                    "{} problem(s) found in day {} input ({})",
+                    errors.len(),
+                    day,
+                    source
+                )
+            }
+            #[cfg(feature = "generate")]
+            Command::Generate { day, seed, size } => {
+                print!("{}", aoc::generate::generate(day, seed, size)?);
+                Ok(())
+            }
+            Command::Submit {
+                day,
+                part,
+                base_url,
+            } => {
+                let answers_path = args.answers.unwrap_or_else(answers::default_path);
+                let reports = run_days(
+                    Some(day),
+                    Some(part),
+                    &InputSource::Default,
+                    &answers::Answers::default(),
+                    &rejected::Rejected::default(),
+                    runner::RunOptions::default(),
+                )?;
+                let Some(answer) = reports[0].part(part).map(|p| &p.answer) else {
+                    color_eyre::eyre::bail!("Day {} part {} is not yet implemented", day, part);
+                };
+                match submit::submit(&client(base_url)?, day, part, answer, &answers_path)? {
+                    submit::SubmitOutcome::Correct => Ok(()),
+                    outcome => {
+                        color_eyre::eyre::bail!("Answer {} was not accepted: {}", answer, outcome)
+                    }
+                }
+            }
+        };
+    }
+
+    let input = match (args.example, args.day) {
+        (Some(example), Some(day)) => example_input(day, example)?,
+        _ => args.input.unwrap_or_default(),
+    };
+    let (answers, rejected) = match (args.example, args.day) {
+        (Some(example), Some(day)) => (example_answers(day, example)?, Default::default()),
+        _ => known_answers(&input, args.answers)?,
+    };
+    let reports = run_days(
+        args.day,
+        args.part,
+        &input,
+        &answers,
+        &rejected,
+        runner::RunOptions {
+            parallel: args.parallel,
+            variants: args.variants,
+        },
+    )?;
+
+    // Parallel runs are timed under contention with the other days, so they'd skew the record
+    if input == InputSource::Default && !args.parallel {
+        let path = timings::default_path();
+        let mut timings = timings::Timings::load(&path)?;
+        timings.record(&reports);
+        timings.save(&path)?;
+    }
+
+    if let Some(output) = report::render(&reports, args.format)? {
+        print!("{}", output);
+    } else if args.time {
+        print!("{}", report::timing_table(&reports));
+    }
+
+    runner::check_answers(&reports)?;
 
     Ok(())
 }
@@ -0,0 +1,65 @@
+//! The TOML files the runner keeps between runs, keyed by day.
+
+use std::{io::ErrorKind, path::Path};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, eyre},
+};
+use serde::{Serialize, de::DeserializeOwned};
+
+/// The key a day's entry is stored under, e.g. `day01`.
+pub fn day_key(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+/// Reads the TOML file at `path`. A missing file is treated as empty.
+pub fn load_toml<T: DeserializeOwned + Default>(path: &Path) -> Result<T> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(T::default()),
+        Err(err) => {
+            return Err(eyre!(err).wrap_err(format!("reading {}", path.display())));
+        }
+    };
+    toml::from_str(&contents).wrap_err_with(|| format!("parsing {}", path.display()))
+}
+
+/// Writes `value` to `path` as TOML, after the comment `header` if there is one. Creates the
+/// file's directory if needed.
+pub fn save_toml<T: Serialize>(path: &Path, header: Option<&str>, value: &T) -> Result<()> {
+    let mut contents = toml::to_string(value)?;
+    if let Some(header) = header {
+        contents = format!("{}\n{}", header, contents);
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .wrap_err_with(|| format!("creating {}", parent.display()))?;
+    }
+    std::fs::write(path, contents).wrap_err_with(|| format!("writing {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::test_util::temp_dir;
+    use test_log::test;
+
+    #[test]
+    fn test_round_trip() {
+        let path = temp_dir("manifest").join("nested").join("manifest.toml");
+        assert!(
+            load_toml::<BTreeMap<String, u64>>(&path)
+                .unwrap()
+                .is_empty()
+        );
+
+        let manifest = BTreeMap::from([(day_key(3), 42u64)]);
+        save_toml(&path, Some("# header"), &manifest).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, "# header\nday03 = 42\n");
+        assert_eq!(load_toml::<BTreeMap<String, u64>>(&path).unwrap(), manifest);
+    }
+}
@@ -0,0 +1,265 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// An error at a specific place in the puzzle input. Parsers create it for the text they choke
+/// on, and each caller that knows where that text came from passes it up with
+/// [`ParseError::within`] or [`ParseError::at`]. Once it has a position it renders like a compiler
+/// diagnostic:
+///
+/// ```text
+/// Invalid direction: `U`
+///  --> 3:1
+///   |
+/// 3 | U42
+///   | ^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+    text: String,
+    /// Byte offset of `text` in the string it was last located in.
+    offset: usize,
+    location: Option<Location>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Location {
+    /// 1-based line number.
+    line: usize,
+    /// 1-based column, in characters.
+    column: usize,
+    source_line: String,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>, text: impl Into<String>) -> Self {
+        ParseError {
+            message: message.into(),
+            text: text.into(),
+            offset: 0,
+            location: None,
+        }
+    }
+
+    /// An error for the character at byte `offset` of `source`, for parsers that work on bytes.
+    /// The offset may point into the middle of a multi-byte character.
+    pub fn at_char(message: impl Into<String>, source: &str, offset: usize) -> Self {
+        let start = (0..=offset.min(source.len()))
+            .rev()
+            .find(|&i| source.is_char_boundary(i))
+            .unwrap_or(0);
+        let text = source[start..].chars().next().map(String::from);
+        ParseError::new(message, text.unwrap_or_default()).at(source, start)
+    }
+
+    /// Places the error in `outer`, given that it came from parsing `inner`, a slice of `outer`.
+    /// If `inner` isn't actually part of `outer`, the error is returned without a position (and
+    /// debug builds panic, since that's a bug in the caller).
+    pub fn within(self, outer: &str, inner: &str) -> Self {
+        let start = (inner.as_ptr() as usize).checked_sub(outer.as_ptr() as usize);
+        let contained = start.is_some_and(|start| start + inner.len() <= outer.len());
+        debug_assert!(contained, "`inner` is not a slice of `outer`");
+        match start {
+            Some(start) if contained => {
+                let offset = start + self.offset;
+                self.at(outer, offset)
+            }
+            _ => self,
+        }
+    }
+
+    /// Places the error at byte `offset` of `source`.
+    pub fn at(mut self, source: &str, offset: usize) -> Self {
+        let bytes = source.as_bytes();
+        let offset = offset.min(bytes.len());
+        let line_start = bytes[..offset]
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        let line_end = bytes[offset..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(bytes.len(), |i| offset + i);
+        // Count characters rather than bytes, skipping UTF-8 continuation bytes
+        let column = bytes[line_start..offset]
+            .iter()
+            .filter(|&&b| b & 0xC0 != 0x80)
+            .count()
+            + 1;
+        self.offset = offset;
+        self.location = Some(Location {
+            line: bytes[..line_start].iter().filter(|&&b| b == b'\n').count() + 1,
+            column,
+            source_line: String::from_utf8_lossy(&bytes[line_start..line_end])
+                .trim_end_matches('\r')
+                .to_string(),
+        });
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// 1-based line and column of the offending text, once the error has been located.
+    pub fn position(&self) -> Option<(usize, usize)> {
+        self.location.as_ref().map(|l| (l.line, l.column))
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        if !self.text.is_empty() {
+            write!(f, ": `{}`", self.text)?;
+        }
+        if let Some(Location {
+            line,
+            column,
+            source_line,
+        }) = &self.location
+        {
+            let gutter = " ".repeat(line.to_string().len());
+            let indent = " ".repeat(column - 1);
+            let carets = "^".repeat(self.text.chars().count().max(1));
+            write!(
+                f,
+                "\n{gutter}--> {line}:{column}\n{gutter} |\n{line} | {source_line}\n{gutter} | {indent}{carets}"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Reports every row of a grid-shaped `input` whose width differs from the first row's.
+pub fn ragged_rows(input: &str) -> Vec<ParseError> {
+    let mut lines = input.lines();
+    let Some(width) = lines.next().map(str::len) else {
+        return vec![ParseError::new("Empty grid", "")];
+    };
+    lines
+        .filter(|line| line.len() != width)
+        .map(|line| {
+            ParseError::new(
+                format!("Row is {} wide, but the first row is {}", line.len(), width),
+                line,
+            )
+            .within(input, line)
+        })
+        .collect()
+}
+
+/// Reports every character of `input`, other than line breaks (`\n` or `\r\n`), that `is_valid`
+/// rejects.
+pub fn invalid_chars(
+    input: &str,
+    message: &str,
+    is_valid: impl Fn(char) -> bool,
+) -> Vec<ParseError> {
+    input
+        .char_indices()
+        .filter(|&(i, c)| {
+            let line_break = c == '\n' || (c == '\r' && input[i + 1..].starts_with('\n'));
+            !line_break && !is_valid(c)
+        })
+        .map(|(i, c)| ParseError::new(message, c).at(input, i))
+        .collect()
+}
+
+/// Parses `text` as a number, describing it as `what` if it isn't one.
+pub fn parse_number<T>(what: &str, text: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text.parse()
+        .map_err(|err| ParseError::new(format!("Invalid {} ({})", what, err), text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_snippet() {
+        let input = "L68\nR30\nU42\n";
+        let line = input.lines().nth(2).unwrap();
+        let err = ParseError::new("Invalid direction", &line[0..1]).within(input, line);
+        assert_eq!(err.position(), Some((3, 1)));
+        assert_eq!(
+            err.to_string(),
+            "Invalid direction: `U`\n --> 3:1\n  |\n3 | U42\n  | ^"
+        );
+    }
+
+    #[test]
+    fn test_nested() {
+        let input = "11-22,95-1x5";
+        let range = input.split(',').nth(1).unwrap();
+        let end = range.split('-').nth(1).unwrap();
+        let err = parse_number::<i64>("range end", end)
+            .unwrap_err()
+            .within(range, end)
+            .within(input, range);
+        assert_eq!(err.position(), Some((1, 10)));
+        assert!(
+            err.to_string()
+                .ends_with("1 | 11-22,95-1x5\n  |          ^^^")
+        );
+    }
+
+    #[test]
+    fn test_at_offset() {
+        let err = ParseError::new("Invalid cell", "é").at("..\n.é.", 4);
+        assert_eq!(err.position(), Some((2, 2)));
+        assert_eq!(err.message(), "Invalid cell");
+    }
+
+    #[test]
+    fn test_at_char() {
+        let line = "12 4é6";
+        let err = ParseError::at_char("Invalid digit", line, 5);
+        assert_eq!(err.position(), Some((1, 5)));
+        assert!(err.to_string().starts_with("Invalid digit: `é`"));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "not a slice")]
+    fn test_within_unrelated() {
+        let _ = ParseError::new("Invalid direction", "U").within("L68", "U");
+    }
+
+    #[test]
+    fn test_unlocated() {
+        assert_eq!(
+            ParseError::new("Invalid direction", "U").to_string(),
+            "Invalid direction: `U`"
+        );
+    }
+
+    #[test]
+    fn test_invalid_chars() {
+        let errors = invalid_chars("..\n.é#\n", "Invalid cell", |c| c == '.');
+        let positions: Vec<_> = errors.iter().map(ParseError::position).collect();
+        assert_eq!(positions, [Some((2, 2)), Some((2, 3))]);
+        assert!(errors[0].to_string().starts_with("Invalid cell: `é`"));
+
+        let errors = invalid_chars("..\r\n.\r.\r", "Invalid cell", |c| c == '.');
+        let positions: Vec<_> = errors.iter().map(ParseError::position).collect();
+        assert_eq!(positions, [Some((2, 2)), Some((2, 4))]);
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        let errors = ragged_rows("...\n..\n...\n....");
+        let positions: Vec<_> = errors.iter().map(ParseError::position).collect();
+        assert_eq!(positions, [Some((2, 1)), Some((4, 1))]);
+        assert_eq!(errors[0].message(), "Row is 2 wide, but the first row is 3");
+    }
+}
@@ -0,0 +1,184 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+};
+
+use aoc::Answer;
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    manifest::{day_key, load_toml, save_toml},
+    submit::SubmitOutcome,
+};
+
+pub const REJECTED_FILE: &str = "rejected.toml";
+
+const HEADER: &str = "\
+# Answers the site rejected, recorded by `cargo run -- submit`.
+#
+# `too_low` is the highest answer that was too low and `too_high` the lowest answer that was too
+# high. Submissions that repeat a rejected answer or fall outside those bounds are blocked.
+";
+
+/// The rejected answers manifest that goes with the answers manifest at `answers_path`.
+pub fn path_for(answers_path: &Path) -> PathBuf {
+    answers_path.with_file_name(REJECTED_FILE)
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct RejectedPart {
+    #[serde(default)]
+    answers: Vec<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    too_low: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    too_high: Option<Answer>,
+}
+
+/// Why an answer is already known to be wrong.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    /// This exact answer was rejected before.
+    Repeated,
+    /// At or below an answer that was too low.
+    TooLow { bound: Answer },
+    /// At or above an answer that was too high.
+    TooHigh { bound: Answer },
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::Repeated => write!(f, "already rejected"),
+            Rejection::TooLow { bound } => write!(f, "{} was already too low", bound),
+            Rejection::TooHigh { bound } => write!(f, "{} was already too high", bound),
+        }
+    }
+}
+
+/// Rejected answers per day (`day01`) and part (`part1`).
+///
+/// ```toml
+/// [day01.part1]
+/// answers = [100, 5000]
+/// too_low = 100
+/// too_high = 5000
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Rejected(BTreeMap<String, BTreeMap<String, RejectedPart>>);
+
+impl Rejected {
+    /// Reads the manifest at `path`. A missing manifest is treated as empty.
+    pub fn load(path: &Path) -> Result<Self> {
+        load_toml(path)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        save_toml(path, Some(HEADER), self)
+    }
+
+    fn get(&self, day: u8, part: u8) -> Option<&RejectedPart> {
+        self.0.get(&day_key(day))?.get(&format!("part{}", part))
+    }
+
+    /// Records a rejected `answer`, narrowing the bounds if the site said which way it was off.
+    pub fn record(&mut self, day: u8, part: u8, answer: &Answer, outcome: &SubmitOutcome) {
+        let rejected = self
+            .0
+            .entry(day_key(day))
+            .or_default()
+            .entry(format!("part{}", part))
+            .or_default();
+        if !rejected.answers.contains(answer) {
+            rejected.answers.push(answer.clone());
+        }
+        let number = answer.as_i128();
+        let bound = |bound: &Option<Answer>| bound.as_ref().and_then(Answer::as_i128);
+        match outcome {
+            SubmitOutcome::TooLow if number > bound(&rejected.too_low) => {
+                rejected.too_low = Some(answer.clone());
+            }
+            SubmitOutcome::TooHigh
+                if bound(&rejected.too_high).is_none_or(|high| number < Some(high)) =>
+            {
+                rejected.too_high = Some(answer.clone());
+            }
+            _ => {}
+        }
+    }
+
+    /// Checks `answer` against the rejected answers and bounds for `day` and `part`.
+    pub fn check(&self, day: u8, part: u8, answer: &Answer) -> Option<Rejection> {
+        let rejected = self.get(day, part)?;
+        if rejected.answers.contains(answer) {
+            return Some(Rejection::Repeated);
+        }
+        let number = answer.as_i128()?;
+        if let Some(bound) = &rejected.too_low
+            && bound.as_i128().is_some_and(|low| number <= low)
+        {
+            return Some(Rejection::TooLow {
+                bound: bound.clone(),
+            });
+        }
+        if let Some(bound) = &rejected.too_high
+            && bound.as_i128().is_some_and(|high| number >= high)
+        {
+            return Some(Rejection::TooHigh {
+                bound: bound.clone(),
+            });
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use test_log::test;
+
+    #[test]
+    fn test_record_and_check() {
+        let mut rejected = Rejected::default();
+        rejected.record(1, 1, &Answer::from(100), &SubmitOutcome::TooLow);
+        rejected.record(1, 1, &Answer::from(50), &SubmitOutcome::TooLow);
+        rejected.record(1, 1, &Answer::from(5000), &SubmitOutcome::TooHigh);
+        rejected.record(1, 1, &Answer::from(4000), &SubmitOutcome::TooHigh);
+        rejected.record(1, 1, &Answer::from(1234), &SubmitOutcome::Incorrect);
+
+        assert_eq!(
+            rejected.check(1, 1, &Answer::from(1234)),
+            Some(Rejection::Repeated)
+        );
+        assert_eq!(
+            rejected.check(1, 1, &Answer::from(99)),
+            Some(Rejection::TooLow {
+                bound: Answer::Signed(100)
+            })
+        );
+        assert_eq!(
+            rejected.check(1, 1, &Answer::from(4500)),
+            Some(Rejection::TooHigh {
+                bound: Answer::Signed(4000)
+            })
+        );
+        assert_eq!(rejected.check(1, 1, &Answer::from(101)), None);
+        assert_eq!(rejected.check(1, 2, &Answer::from(99)), None);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let mut rejected = Rejected::default();
+        rejected.record(2, 2, &Answer::from(7), &SubmitOutcome::TooLow);
+        let path = temp_dir("rejected").join("rejected.toml");
+        rejected.save(&path).unwrap();
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("[day02.part2]\nanswers = [7]\ntoo_low = 7\n"));
+        let reloaded = Rejected::load(&path).unwrap();
+        assert!(reloaded.check(2, 2, &Answer::from(6)).is_some());
+    }
+}
@@ -0,0 +1,237 @@
+use std::{fmt::Write, time::Duration};
+
+use aoc::Answer;
+use clap::ValueEnum;
+use color_eyre::Result;
+use serde::Serialize;
+
+use crate::{answers::Verification, runner::DayReport};
+
+/// How the runner reports answers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Log lines through `tracing`.
+    #[default]
+    Text,
+    /// A single JSON array of records on stdout.
+    Json,
+    /// One JSON record per line on stdout.
+    Ndjson,
+}
+
+/// One day and part's result, as emitted by the JSON output formats.
+#[derive(Debug, Serialize)]
+pub struct Record<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub answer: &'a Answer,
+    pub answer_type: &'a str,
+    pub parse_duration_ns: u64,
+    pub duration_ns: u64,
+    pub verification: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<&'a Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejection: Option<String>,
+}
+
+pub fn records(reports: &[DayReport]) -> Vec<Record<'_>> {
+    reports
+        .iter()
+        .flat_map(|report| {
+            report.parts.iter().map(|part| Record {
+                day: report.day,
+                part: part.part,
+                answer: &part.answer,
+                answer_type: part.answer_type,
+                parse_duration_ns: report.parse_duration.as_nanos() as u64,
+                duration_ns: part.duration.as_nanos() as u64,
+                verification: part.verification.status(),
+                expected: match &part.verification {
+                    Verification::Incorrect { expected } => Some(expected),
+                    _ => None,
+                },
+                rejection: match &part.verification {
+                    Verification::Rejected(rejection) => Some(rejection.to_string()),
+                    _ => None,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Renders `reports` in the given JSON format. Returns `None` for [`OutputFormat::Text`], which
+/// is reported through log lines as the days run instead.
+pub fn render(reports: &[DayReport], format: OutputFormat) -> Result<Option<String>> {
+    let records = records(reports);
+    match format {
+        OutputFormat::Text => Ok(None),
+        OutputFormat::Json => Ok(Some(serde_json::to_string_pretty(&records)? + "\n")),
+        OutputFormat::Ndjson => {
+            let mut output = String::new();
+            for record in &records {
+                output += &serde_json::to_string(record)?;
+                output.push('\n');
+            }
+            Ok(Some(output))
+        }
+    }
+}
+
+const HEADERS: [&str; 5] = ["Day", "Parse", "Part 1", "Part 2", "Total"];
+
+pub fn format_duration(duration: Option<Duration>) -> String {
+    match duration {
+        Some(duration) => format!("{:.2?}", duration),
+        None => "-".to_string(),
+    }
+}
+
+/// Renders an aligned table of parse and solve times per day, with a total row at the bottom.
+pub fn timing_table(reports: &[DayReport]) -> String {
+    let rows: Vec<[String; 5]> = reports
+        .iter()
+        .map(|report| {
+            [
+                format!("{:02}", report.day),
+                format_duration(Some(report.parse_duration)),
+                format_duration(report.part(1).map(|p| p.duration)),
+                format_duration(report.part(2).map(|p| p.duration)),
+                format_duration(Some(report.total_duration())),
+            ]
+        })
+        .collect();
+
+    let sum = |f: &dyn Fn(&DayReport) -> Option<Duration>| -> Option<Duration> {
+        let durations: Vec<Duration> = reports.iter().filter_map(f).collect();
+        (!durations.is_empty()).then(|| durations.into_iter().sum())
+    };
+    let total = [
+        "Total".to_string(),
+        format_duration(sum(&|r| Some(r.parse_duration))),
+        format_duration(sum(&|r| r.part(1).map(|p| p.duration))),
+        format_duration(sum(&|r| r.part(2).map(|p| p.duration))),
+        format_duration(sum(&|r| Some(r.total_duration()))),
+    ];
+
+    table(HEADERS, &rows, Some(&total))
+}
+
+/// Renders an aligned Markdown-style table. The first column is left-aligned and the rest
+/// right-aligned. A `footer` row goes below a second separator.
+pub fn table<const N: usize>(
+    headers: [&str; N],
+    rows: &[[String; N]],
+    footer: Option<&[String; N]>,
+) -> String {
+    let mut widths = headers.map(str::len);
+    for row in rows.iter().chain(footer) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    let write_row = |table: &mut String, cells: &[&str]| {
+        let line = cells
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (cell, width))| {
+                if i == 0 {
+                    format!(" {:<width$} ", cell)
+                } else {
+                    format!(" {:>width$} ", cell)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("|");
+        writeln!(table, "|{}|", line).unwrap();
+    };
+    let separator = |table: &mut String| {
+        let line = widths
+            .iter()
+            .map(|width| "-".repeat(width + 2))
+            .collect::<Vec<_>>()
+            .join("|");
+        writeln!(table, "|{}|", line).unwrap();
+    };
+
+    write_row(&mut table, &headers);
+    separator(&mut table);
+    for row in rows {
+        write_row(&mut table, &row.each_ref().map(String::as_str));
+    }
+    if let Some(footer) = footer {
+        separator(&mut table);
+        write_row(&mut table, &footer.each_ref().map(String::as_str));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::PartReport;
+    use test_log::test;
+
+    fn part(part: u8, micros: u64) -> PartReport {
+        PartReport {
+            part,
+            answer: Answer::from(0u64),
+            answer_type: "u64",
+            duration: Duration::from_micros(micros),
+            verification: Verification::Unknown,
+            variants: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_timing_table() {
+        let reports = vec![
+            DayReport {
+                day: 1,
+                parse_duration: Duration::from_micros(10),
+                parts: vec![part(1, 20), part(2, 30)],
+            },
+            DayReport {
+                day: 2,
+                parse_duration: Duration::from_micros(1),
+                parts: vec![part(2, 2)],
+            },
+        ];
+        let table = timing_table(&reports);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert!(
+            lines
+                .iter()
+                .all(|l| l.chars().count() == lines[0].chars().count())
+        );
+        assert!(lines[2].starts_with("| 01 "));
+        assert!(lines[3].contains(" - "));
+        assert!(lines[5].starts_with("| Total "));
+        assert!(lines[5].ends_with(" 63.00µs |"));
+    }
+
+    #[test]
+    fn test_render_ndjson() {
+        let mut incorrect = part(2, 30);
+        incorrect.verification = Verification::Incorrect {
+            expected: Answer::from(42),
+        };
+        let reports = vec![DayReport {
+            day: 1,
+            parse_duration: Duration::from_nanos(5),
+            parts: vec![part(1, 20), incorrect],
+        }];
+        let output = render(&reports, OutputFormat::Ndjson).unwrap().unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines[0],
+            r#"{"day":1,"part":1,"answer":0,"answer_type":"u64","parse_duration_ns":5,"duration_ns":20000,"verification":"unknown"}"#
+        );
+        assert!(lines[1].ends_with(r#""verification":"incorrect","expected":42}"#));
+        assert!(render(&reports, OutputFormat::Text).unwrap().is_none());
+    }
+}
@@ -1,57 +1,498 @@
+use std::time::{Duration, Instant};
+
+use aoc::{Answer, NotImplemented, Solution, Variant};
 use color_eyre::Result;
-use tracing::info;
+use rayon::prelude::*;
+use tracing::{Span, error, info, warn};
+
+use crate::{
+    answers::{Answers, Verification},
+    rejected::Rejected,
+    report::format_duration,
+};
 
 macro_rules! days {
     ($($day_num:literal => $day_mod:ident),* $(,)?) => {
-        pub fn run_days(day: Option<u8>, part: Option<u8>) -> Result<()> {
-            match day {
-                $(
-                    Some($day_num) => $crate::runner::run_day($day_num, part, $day_mod::part1, $day_mod::part2, $day_mod::INPUT)?,
-                )*
-                Some(d) => color_eyre::eyre::bail!("Day {} is not yet implemented", d),
+        /// Runs `day` (or every day) and returns a report per day, in day order.
+        pub fn run_days(
+            day: Option<u8>,
+            part: Option<u8>,
+            source: &aoc::input::InputSource,
+            answers: &$crate::answers::Answers,
+            rejected: &$crate::rejected::Rejected,
+            options: $crate::runner::RunOptions,
+        ) -> Result<Vec<$crate::runner::DayReport>> {
+            let runners: &[(u8, $crate::runner::DayRunner)] = &[
+                $(($day_num, $crate::runner::run_day::<$day_mod::Day>),)*
+            ];
+            let runners = match day {
+                Some(d) => match runners.iter().find(|(day_num, _)| *day_num == d) {
+                    Some(runner) => std::slice::from_ref(runner),
+                    None => color_eyre::eyre::bail!("Day {} is not yet implemented", d),
+                },
                 None => {
-                    $(
-                        $crate::runner::run_day($day_num, None, $day_mod::part1, $day_mod::part2, $day_mod::INPUT)?;
-                    )*
+                    if *source != aoc::input::InputSource::Default {
+                        color_eyre::eyre::bail!("--input requires --day");
+                    }
+                    runners
                 }
+            };
+            let run = |(day_num, runner): &(u8, $crate::runner::DayRunner)| {
+                let input = aoc::input::load(*day_num, source)?;
+                runner(*day_num, part, &input, answers, rejected, options)
+            };
+            if options.parallel {
+                $crate::runner::run_parallel(runners, run)
+            } else {
+                runners.iter().map(run).collect()
             }
-            Ok(())
         }
     };
 }
 
 pub(crate) use days;
 
-pub fn run_day<T1, T2>(
+/// How [`run_day`] runs each day.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunOptions {
+    /// Schedule days and parts concurrently on the global rayon pool.
+    pub parallel: bool,
+    /// Also run every named variant of each part and fail if any disagrees with the main one.
+    pub variants: bool,
+}
+
+/// The outcome of running one part of a day.
+#[derive(Debug, Clone)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Answer,
+    /// Rust type the solution returned the answer as, e.g. `u64`.
+    pub answer_type: &'static str,
+    pub duration: Duration,
+    pub verification: Verification,
+    /// Timings of the part's named variants, which all returned the same answer.
+    pub variants: Vec<VariantReport>,
+}
+
+/// How long one of a part's named variants took.
+#[derive(Debug, Clone)]
+pub struct VariantReport {
+    pub name: &'static str,
+    pub duration: Duration,
+}
+
+/// The outcome of running a day: how long parsing took and the result of each part that ran.
+#[derive(Debug, Clone)]
+pub struct DayReport {
+    pub day: u8,
+    pub parse_duration: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+impl PartReport {
+    fn log(&self) {
+        match self.verification {
+            Verification::Unknown => info!("Part {}: {}", self.part, self.answer),
+            Verification::Correct => {
+                info!("Part {}: {} {}", self.part, self.answer, self.verification)
+            }
+            Verification::Incorrect { .. } | Verification::Rejected(_) => {
+                error!("Part {}: {} {}", self.part, self.answer, self.verification)
+            }
+        }
+        for variant in &self.variants {
+            info!(
+                "Part {}@{}: {} ({})",
+                self.part,
+                variant.name,
+                self.answer,
+                format_duration(Some(variant.duration))
+            );
+        }
+    }
+}
+
+impl DayReport {
+    /// Parse time plus the solve time of every part.
+    pub fn total_duration(&self) -> Duration {
+        self.parse_duration + self.parts.iter().map(|p| p.duration).sum::<Duration>()
+    }
+
+    pub fn part(&self, part: u8) -> Option<&PartReport> {
+        self.parts.iter().find(|p| p.part == part)
+    }
+}
+
+/// A monomorphized [`run_day`], so days with different solution types can share a table.
+pub type DayRunner = fn(u8, Option<u8>, &str, &Answers, &Rejected, RunOptions) -> Result<DayReport>;
+
+fn day_span(day: u8) -> Span {
+    tracing::info_span!("day", day = %format!("{:02}", day))
+}
+
+/// Runs every runner on the rayon pool, collecting the reports in the original order. Answers
+/// are logged once everything has finished so they come out in day order too.
+pub fn run_parallel<F>(runners: &[(u8, DayRunner)], run: F) -> Result<Vec<DayReport>>
+where
+    F: Fn(&(u8, DayRunner)) -> Result<DayReport> + Sync,
+{
+    // Rayon's worker threads don't inherit the current span, so carry it over explicitly
+    let parent = Span::current();
+    let reports = runners
+        .par_iter()
+        .map(|runner| parent.in_scope(|| run(runner)))
+        .collect::<Result<Vec<_>>>()?;
+    for report in &reports {
+        info!("Day {}", report.day);
+        let _span = day_span(report.day).entered();
+        report.parts.iter().for_each(PartReport::log);
+    }
+    Ok(reports)
+}
+
+/// Runs the selected parts of a day, timing the parse and each part and checking each answer
+/// against the known and rejected answers.
+///
+/// With `options.variants`, each part's variants run after it and must return the same answer.
+/// With `options.parallel`, both parts run at once on the rayon pool and logging the answers is
+/// left to the caller. Parts that use rayon themselves share the same pool, so nothing is
+/// oversubscribed. Rayon jobs don't inherit the current span though, and a worker waiting inside
+/// another day can steal them, so such parts must enter `Span::current()` in their parallel
+/// closures for their logs to stay under the right day.
+pub fn run_day<S: Solution>(
     day: u8,
     part: Option<u8>,
-    part1_fn: fn(&str) -> Result<T1>,
-    part2_fn: fn(&str) -> Result<T2>,
     input: &str,
-) -> Result<()>
+    answers: &Answers,
+    rejected: &Rejected,
+    options: RunOptions,
+) -> Result<DayReport>
 where
-    T1: std::fmt::Display,
-    T2: std::fmt::Display,
+    S::Parsed: Sync,
 {
-    info!("Day {}", day);
-    let day_name = format!("{:02}", day);
-    let _span = tracing::info_span!("day", day = %day_name).entered();
+    if !options.parallel {
+        info!("Day {}", day);
+    }
+    let span = day_span(day);
+    let _enter = span.enter();
 
-    if part.is_none() || part == Some(1) {
-        let result = part1_fn(input)?;
-        info!("Part 1: {}", result);
+    if let Some(p) = part
+        && p != 1
+        && p != 2
+    {
+        color_eyre::eyre::bail!("Part {} is invalid. Must be 1 or 2.", p);
     }
 
-    if part.is_none() || part == Some(2) {
-        let result = part2_fn(input)?;
-        info!("Part 2: {}", result);
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse_duration = start.elapsed();
+
+    let part1 = || -> Result<Option<PartReport>> {
+        if part.is_some_and(|p| p != 1) {
+            return Ok(None);
+        }
+        let start = Instant::now();
+        let Some(result) = implemented(1, S::part1(&parsed))? else {
+            return Ok(None);
+        };
+        let mut report = report_part(day, 1, result, start.elapsed(), answers, rejected);
+        if options.variants {
+            report.variants = run_variants(&report, &parsed, &S::part1_variants())?;
+        }
+        Ok(Some(report))
+    };
+    let part2 = || -> Result<Option<PartReport>> {
+        if part.is_some_and(|p| p != 2) {
+            return Ok(None);
+        }
+        let start = Instant::now();
+        let Some(result) = implemented(2, S::part2(&parsed))? else {
+            return Ok(None);
+        };
+        let mut report = report_part(day, 2, result, start.elapsed(), answers, rejected);
+        if options.variants {
+            report.variants = run_variants(&report, &parsed, &S::part2_variants())?;
+        }
+        Ok(Some(report))
+    };
+
+    let parts = if options.parallel {
+        let (part1, part2) = rayon::join(|| span.in_scope(part1), || span.in_scope(part2));
+        [part1?, part2?].into_iter().flatten().collect()
+    } else {
+        let mut parts = Vec::new();
+        if let Some(report) = part1()? {
+            report.log();
+            parts.push(report);
+        }
+        if let Some(report) = part2()? {
+            report.log();
+            parts.push(report);
+        }
+        parts
+    };
+
+    Ok(DayReport {
+        day,
+        parse_duration,
+        parts,
+    })
+}
+
+/// `None` if the part returned [`NotImplemented`], so a scaffolded day doesn't stop the run.
+fn implemented<T>(part: u8, result: Result<T>) -> Result<Option<T>> {
+    match result {
+        Err(err) if err.is::<NotImplemented>() => {
+            warn!("Part {}: not yet implemented", part);
+            Ok(None)
+        }
+        result => result.map(Some),
+    }
+}
+
+fn report_part<T: Into<Answer>>(
+    day: u8,
+    part: u8,
+    result: T,
+    duration: Duration,
+    answers: &Answers,
+    rejected: &Rejected,
+) -> PartReport {
+    let answer = result.into();
+    PartReport {
+        part,
+        verification: answers.verify_with_rejected(rejected, day, part, &answer),
+        answer,
+        answer_type: std::any::type_name::<T>(),
+        duration,
+        variants: Vec::new(),
     }
+}
 
-    if let Some(p) = part {
-        if p != 1 && p != 2 {
-            color_eyre::eyre::bail!("Part {} is invalid. Must be 1 or 2.", p);
+/// Runs each variant of a part against the same parsed input, failing if its answer differs from
+/// the one in `report`.
+fn run_variants<P, T: Into<Answer>>(
+    report: &PartReport,
+    parsed: &P,
+    variants: &[Variant<P, T>],
+) -> Result<Vec<VariantReport>> {
+    let mut reports = Vec::new();
+    for variant in variants {
+        let start = Instant::now();
+        let answer = (variant.solve)(parsed)?.into();
+        let duration = start.elapsed();
+        if answer != report.answer {
+            color_eyre::eyre::bail!(
+                "Part {}@{} returned {}, but part {} returned {}",
+                report.part,
+                variant.name,
+                answer,
+                report.part,
+                report.answer
+            );
         }
+        reports.push(VariantReport {
+            name: variant.name,
+            duration,
+        });
     }
+    Ok(reports)
+}
 
+/// Fails if any answer in `reports` didn't match the known answers.
+pub fn check_answers(reports: &[DayReport]) -> Result<()> {
+    let mismatches = reports
+        .iter()
+        .flat_map(|r| &r.parts)
+        .filter(|p| {
+            matches!(
+                p.verification,
+                Verification::Incorrect { .. } | Verification::Rejected(_)
+            )
+        })
+        .count();
+    if mismatches > 0 {
+        color_eyre::eyre::bail!("{} answer(s) did not match the known answers", mismatches);
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fmt::Debug,
+        sync::{Arc, Mutex},
+    };
+
+    use tracing::{
+        Dispatch, Id, Subscriber,
+        field::{Field, Visit},
+        span::Attributes,
+    };
+    use tracing_subscriber::{
+        layer::{Context, Layer},
+        prelude::*,
+        registry::LookupSpan,
+    };
+
+    use super::*;
+    use test_log::test;
+
+    /// A day with only part 1 written, like one fresh from `new`.
+    struct Unfinished;
+
+    impl Solution for Unfinished {
+        type Parsed = ();
+        type Part1 = u64;
+        type Part2 = u64;
+
+        fn parse(_input: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn part1(_parsed: &()) -> Result<u64> {
+            Ok(1)
+        }
+
+        fn part2(_parsed: &()) -> Result<u64> {
+            Err(NotImplemented.into())
+        }
+    }
+
+    #[test]
+    fn test_unimplemented_part_is_skipped() {
+        for parallel in [false, true] {
+            let options = RunOptions {
+                parallel,
+                ..Default::default()
+            };
+            let report = run_day::<Unfinished>(
+                8,
+                None,
+                "",
+                &Answers::default(),
+                &Rejected::default(),
+                options,
+            )
+            .unwrap();
+            assert_eq!(report.part(1).unwrap().answer, Answer::from(1u64));
+            assert!(report.part(2).is_none());
+        }
+    }
+
+    /// A part that fans out over rayon, logging each job in a span tagged with the day it
+    /// belongs to.
+    struct Fanout<const DAY: u8>;
+
+    impl<const DAY: u8> Fanout<DAY> {
+        fn fan_out() -> Result<u64> {
+            let span = Span::current();
+            Ok((0..256u64)
+                .into_par_iter()
+                .map(|n| {
+                    span.in_scope(|| {
+                        let _job =
+                            tracing::info_span!("job", day = %format!("{:02}", DAY)).entered();
+                        n
+                    })
+                })
+                .sum())
+        }
+    }
+
+    impl<const DAY: u8> Solution for Fanout<DAY> {
+        type Parsed = ();
+        type Part1 = u64;
+        type Part2 = u64;
+
+        fn parse(_input: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn part1(_parsed: &()) -> Result<u64> {
+            Self::fan_out()
+        }
+
+        fn part2(_parsed: &()) -> Result<u64> {
+            Self::fan_out()
+        }
+    }
+
+    /// The `day` field of a span.
+    struct DayField(String);
+
+    impl Visit for DayField {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            if field.name() == "day" {
+                self.0 = format!("{:?}", value);
+            }
+        }
+    }
+
+    /// Records, for every `job` span, its own day and the day of the `day` span it was created
+    /// under, empty if there's none.
+    #[derive(Clone, Default)]
+    struct JobDays(Arc<Mutex<Vec<(String, String)>>>);
+
+    impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for JobDays {
+        fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+            let span = ctx.span(id).expect("span was just created");
+            let mut day = DayField(String::new());
+            attrs.record(&mut day);
+            if span.name() == "job" {
+                let parent_day = span
+                    .scope()
+                    .skip(1)
+                    .find(|parent| parent.name() == "day")
+                    .and_then(|parent| parent.extensions().get::<DayField>().map(|d| d.0.clone()))
+                    .unwrap_or_default();
+                self.0.lock().unwrap().push((day.0.clone(), parent_day));
+            }
+            span.extensions_mut().insert(day);
+        }
+    }
+
+    #[test]
+    fn test_parallel_jobs_log_under_their_day() {
+        let jobs = JobDays::default();
+        let dispatch = Dispatch::new(tracing_subscriber::registry().with(jobs.clone()));
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .start_handler({
+                let dispatch = dispatch.clone();
+                move |_| std::mem::forget(tracing::dispatcher::set_default(&dispatch))
+            })
+            .build()
+            .unwrap();
+        let _guard = tracing::dispatcher::set_default(&dispatch);
+
+        let runners: &[(u8, DayRunner)] = &[
+            (1, run_day::<Fanout<1>>),
+            (2, run_day::<Fanout<2>>),
+            (3, run_day::<Fanout<3>>),
+        ];
+        let options = RunOptions {
+            parallel: true,
+            ..Default::default()
+        };
+        pool.install(|| {
+            run_parallel(runners, |(day, runner)| {
+                runner(
+                    *day,
+                    None,
+                    "",
+                    &Answers::default(),
+                    &Rejected::default(),
+                    options,
+                )
+            })
+        })
+        .unwrap();
+
+        let jobs = jobs.0.lock().unwrap();
+        assert_eq!(jobs.len(), 256 * 6);
+        for (day, parent_day) in jobs.iter() {
+            assert_eq!(day, parent_day);
+        }
+    }
+}
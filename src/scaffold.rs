@@ -0,0 +1,91 @@
+use std::path::{Path, PathBuf};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+use tracing::info;
+
+const DAY_TEMPLATE: &str = r#"use color_eyre::Result;
+use tracing::{debug, instrument};
+
+use crate::{NotImplemented, Solution};
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    #[instrument(skip(input))]
+    fn parse(input: &str) -> Result<Vec<String>> {
+        Ok(input.trim().lines().map(String::from).collect())
+    }
+
+    #[instrument(skip(lines))]
+    fn part1(lines: &Vec<String>) -> Result<usize> {
+        debug!(lines = lines.len());
+        Err(NotImplemented.into())
+    }
+
+    #[instrument(skip(lines))]
+    fn part2(lines: &Vec<String>) -> Result<usize> {
+        debug!(lines = lines.len());
+        Err(NotImplemented.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    const TEST_INPUT1: &str = include_str!("input/test1.txt");
+
+    #[test]
+    fn test_parse() {
+        assert!(Day::validate(TEST_INPUT1).is_empty());
+    }
+}
+"#;
+
+const EXAMPLES_TEMPLATE: &str = r#"# Examples checked by `cargo test --test examples`. Add more with `file = "testN.txt"` or an
+# inline `input = "..."`.
+[[example]]
+file = "test1.txt"
+# part1 =
+# part2 =
+"#;
+
+fn src_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src")
+}
+
+/// Creates `src/dayNN/` from the day template. build.rs picks it up on the next build.
+pub fn new_day(day: u8) -> Result<()> {
+    if !(1..=25).contains(&day) {
+        bail!("Day {} is invalid. Must be between 1 and 25.", day);
+    }
+    let day_dir = src_dir().join(format!("day{:02}", day));
+    let mod_path = day_dir.join("mod.rs");
+    if mod_path.exists() {
+        bail!("Day {} already exists at {}", day, mod_path.display());
+    }
+
+    std::fs::create_dir_all(day_dir.join("input"))
+        .wrap_err_with(|| format!("creating {}", day_dir.display()))?;
+    write(&mod_path, DAY_TEMPLATE)?;
+    write(&day_dir.join("examples.toml"), EXAMPLES_TEMPLATE)?;
+    write(&day_dir.join("input").join("test1.txt"), "")?;
+
+    info!("Created {}", day_dir.display());
+    info!(
+        "Paste the example from the puzzle into input/test1.txt and fill in the expected answers in examples.toml"
+    );
+    Ok(())
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    std::fs::write(path, contents).wrap_err_with(|| format!("writing {}", path.display()))
+}
@@ -0,0 +1,76 @@
+use std::fmt::{self, Display};
+
+use color_eyre::Result;
+
+use crate::{Answer, parse::ParseError};
+
+/// A named alternative implementation of a part, such as a naive version kept next to an
+/// optimized one so the two can be cross-checked and benchmarked against each other.
+pub struct Variant<P, T> {
+    pub name: &'static str,
+    pub solve: fn(&P) -> Result<T>,
+}
+
+/// Returned by a part that hasn't been written yet, as in a freshly scaffolded day. The runner
+/// reports such parts as unimplemented instead of failing the whole run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotImplemented;
+
+impl Display for NotImplemented {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not yet implemented")
+    }
+}
+
+impl std::error::Error for NotImplemented {}
+
+/// A day's puzzle solution.
+///
+/// The input is parsed once into [`Solution::Parsed`], which both parts then solve from. This
+/// lets the runner and benchmarks time parsing separately from solving.
+pub trait Solution {
+    /// The puzzle input after parsing.
+    type Parsed;
+    /// The answer to part 1.
+    type Part1: Display + Into<Answer>;
+    /// The answer to part 2.
+    type Part2: Display + Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Parsed>;
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1>;
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2>;
+
+    /// Named implementations of part 1 that should give the same answer as [`Solution::part1`].
+    fn part1_variants() -> Vec<Variant<Self::Parsed, Self::Part1>> {
+        Vec::new()
+    }
+
+    /// Named implementations of part 2 that should give the same answer as [`Solution::part2`].
+    fn part2_variants() -> Vec<Variant<Self::Parsed, Self::Part2>> {
+        Vec::new()
+    }
+
+    /// Checks `input` against the assumptions the solution makes about it and returns every
+    /// violation. By default only parse errors are reported.
+    fn validate(input: &str) -> Vec<ParseError> {
+        match Self::parse(input) {
+            Ok(_) => Vec::new(),
+            Err(err) => vec![
+                err.downcast::<ParseError>()
+                    .unwrap_or_else(|err| ParseError::new(err.to_string(), "")),
+            ],
+        }
+    }
+
+    /// Parses `input` and solves part 1.
+    fn solve_part1(input: &str) -> Result<Self::Part1> {
+        Self::part1(&Self::parse(input)?)
+    }
+
+    /// Parses `input` and solves part 2.
+    fn solve_part2(input: &str) -> Result<Self::Part2> {
+        Self::part2(&Self::parse(input)?)
+    }
+}
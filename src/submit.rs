@@ -0,0 +1,286 @@
+use std::{
+    fmt::{Display, Formatter},
+    path::Path,
+    time::Duration,
+};
+
+use aoc::Answer;
+use color_eyre::{Result, eyre::bail};
+use tracing::{error, info, warn};
+
+use crate::{
+    answers::Answers,
+    client::Client,
+    rejected::{self, Rejected},
+};
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about which direction.
+    Incorrect,
+    /// The part was already solved, or isn't unlocked yet.
+    AlreadySolved,
+    /// An answer was submitted too recently. Holds how long is left to wait.
+    RateLimited(Option<Duration>),
+    /// A response we don't recognize. Holds the page's main text.
+    Unrecognized(String),
+}
+
+impl Display for SubmitOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "correct"),
+            SubmitOutcome::TooHigh => write!(f, "too high"),
+            SubmitOutcome::TooLow => write!(f, "too low"),
+            SubmitOutcome::Incorrect => write!(f, "incorrect"),
+            SubmitOutcome::AlreadySolved => write!(f, "already solved"),
+            SubmitOutcome::RateLimited(Some(wait)) => write!(f, "rate limited for {:?}", wait),
+            SubmitOutcome::RateLimited(None) => write!(f, "rate limited"),
+            SubmitOutcome::Unrecognized(text) => write!(f, "unrecognized response: {}", text),
+        }
+    }
+}
+
+/// Text of the page's `<article>` (or the whole page if there isn't one), without tags.
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map(|(article, _)| article.split_once('>').map_or(article, |(_, body)| body))
+        .unwrap_or(html);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses a wait like `1m 23s` or `37s` out of "You have 1m 23s left to wait."
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    let mut seconds = 0;
+    for part in wait.split_whitespace() {
+        let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        let value: u64 = value.parse().ok()?;
+        seconds += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+pub fn parse_response(html: &str) -> SubmitOutcome {
+    let text = article_text(html);
+    if text.contains("That's the right answer") {
+        SubmitOutcome::Correct
+    } else if text.contains("You gave an answer too recently") {
+        SubmitOutcome::RateLimited(parse_wait(&text))
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            SubmitOutcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            SubmitOutcome::TooLow
+        } else {
+            SubmitOutcome::Incorrect
+        }
+    } else if text.contains("You don't seem to be solving the right level") {
+        SubmitOutcome::AlreadySolved
+    } else {
+        SubmitOutcome::Unrecognized(text)
+    }
+}
+
+/// Submits `answer` for `day` and `part`. Correct answers are recorded in the manifest at
+/// `answers_path` and wrong ones in the rejected answers manifest next to it. Answers that were
+/// already rejected, or fall outside the bounds the site reported, aren't submitted.
+pub fn submit(
+    client: &Client,
+    day: u8,
+    part: u8,
+    answer: &Answer,
+    answers_path: &Path,
+) -> Result<SubmitOutcome> {
+    let mut answers = Answers::load(answers_path)?;
+    if let Some(known) = answers.get(day, part) {
+        bail!(
+            "Day {} part {} is already solved with answer {}",
+            day,
+            part,
+            known
+        );
+    }
+
+    let rejected_path = rejected::path_for(answers_path);
+    let mut rejected = Rejected::load(&rejected_path)?;
+    if let Some(rejection) = rejected.check(day, part, answer) {
+        bail!("Not submitting {}: {}", answer, rejection);
+    }
+
+    info!("Submitting {} for day {} part {}", answer, day, part);
+    let outcome = parse_response(&client.submit(day, part, &answer.to_string())?);
+    match &outcome {
+        SubmitOutcome::Correct => {
+            info!("✔ {} is correct", answer);
+            answers.record(day, part, answer.clone());
+            answers.save(answers_path)?;
+            info!("Recorded answer in {}", answers_path.display());
+        }
+        SubmitOutcome::TooHigh | SubmitOutcome::TooLow | SubmitOutcome::Incorrect => {
+            error!("✘ {} is {}", answer, outcome);
+            rejected.record(day, part, answer, &outcome);
+            rejected.save(&rejected_path)?;
+            info!("Recorded rejected answer in {}", rejected_path.display());
+        }
+        _ => warn!("{}", outcome),
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{stub_server, temp_dir, test_client};
+    use test_log::test;
+
+    fn page(article: &str) -> String {
+        format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            article
+        )
+    }
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(
+            parse_response(&page(
+                "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer."
+            )),
+            SubmitOutcome::Correct
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too high.  If you're stuck, ..."
+            )),
+            SubmitOutcome::TooHigh
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            SubmitOutcome::TooLow
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer.")),
+            SubmitOutcome::Incorrect
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            SubmitOutcome::AlreadySolved
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait."
+            )),
+            SubmitOutcome::RateLimited(Some(Duration::from_secs(83)))
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently.  You have 37s left to wait."
+            )),
+            SubmitOutcome::RateLimited(Some(Duration::from_secs(37)))
+        );
+        assert_eq!(
+            parse_response("<p>Something else</p>"),
+            SubmitOutcome::Unrecognized("Something else".to_string())
+        );
+    }
+
+    #[test]
+    fn test_submit_records_correct_answer() {
+        let dir = temp_dir("submit");
+        let (base_url, requests) = stub_server(vec![(200, page("That's the right answer!"))]);
+        let answers_path = dir.join("answers.toml");
+        let outcome = submit(
+            &test_client(&base_url, &dir),
+            3,
+            2,
+            &Answer::from(12345),
+            &answers_path,
+        )
+        .unwrap();
+        assert_eq!(outcome, SubmitOutcome::Correct);
+
+        let request = requests.recv().unwrap();
+        assert_eq!(request.request_line, "POST /2025/day/3/answer HTTP/1.1");
+        assert_eq!(request.body, "level=2&answer=12345");
+
+        let answers = Answers::load(&answers_path).unwrap();
+        assert_eq!(answers.get(3, 2), Some(&Answer::from(12345)));
+
+        let err = submit(
+            &test_client(&base_url, &dir),
+            3,
+            2,
+            &Answer::from(12345),
+            &answers_path,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("already solved"));
+    }
+
+    #[test]
+    fn test_submit_wrong_answer() {
+        let dir = temp_dir("submit-wrong");
+        let (base_url, _requests) = stub_server(vec![(
+            200,
+            page("That's not the right answer; your answer is too low."),
+        )]);
+        let answers_path = dir.join("answers.toml");
+        let outcome = submit(
+            &test_client(&base_url, &dir),
+            1,
+            1,
+            &Answer::from(7),
+            &answers_path,
+        )
+        .unwrap();
+        assert_eq!(outcome, SubmitOutcome::TooLow);
+        assert!(!answers_path.exists());
+
+        // Both are ruled out without contacting the site, which only answers once
+        let err = submit(
+            &test_client(&base_url, &dir),
+            1,
+            1,
+            &Answer::from(7),
+            &answers_path,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("already rejected"));
+        let err = submit(
+            &test_client(&base_url, &dir),
+            1,
+            1,
+            &Answer::from(5),
+            &answers_path,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("7 was already too low"));
+    }
+}
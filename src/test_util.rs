@@ -0,0 +1,84 @@
+//! Helpers shared by the runner's tests.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+use crate::client::Client;
+
+/// A request received by [`stub_server`].
+pub struct StubRequest {
+    pub request_line: String,
+    pub headers: Vec<String>,
+    pub body: String,
+}
+
+/// Serves one canned response per entry in `responses` on a local port. Returns the base URL
+/// and a channel of the requests it received.
+pub fn stub_server(responses: Vec<(u16, String)>) -> (String, mpsc::Receiver<StubRequest>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for (status, body) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                headers.push(line.trim().to_string());
+            }
+            let length = headers
+                .iter()
+                .find_map(|h| {
+                    h.to_lowercase()
+                        .strip_prefix("content-length: ")?
+                        .parse()
+                        .ok()
+                })
+                .unwrap_or(0);
+            let mut request_body = vec![0; length];
+            reader.read_exact(&mut request_body).unwrap();
+            sender
+                .send(StubRequest {
+                    request_line: request_line.trim().to_string(),
+                    headers,
+                    body: String::from_utf8(request_body).unwrap(),
+                })
+                .unwrap();
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+    (base_url, receiver)
+}
+
+/// A fresh scratch directory under the system temp dir.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+pub fn test_client(base_url: &str, dir: &Path) -> Client {
+    Client::new(base_url, "abc123").with_throttle(dir.join("last-request"), Duration::ZERO)
+}
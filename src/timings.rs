@@ -0,0 +1,76 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    manifest::{day_key, load_toml, save_toml},
+    runner::DayReport,
+};
+
+/// Where the last measured runtimes are kept between runs.
+pub fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("aoc")
+        .join("timings.toml")
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct DayTimings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parse_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2_ns: Option<u64>,
+}
+
+/// The last measured runtime of each day and part against the real input, keyed by day
+/// (`day01`).
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Timings(BTreeMap<String, DayTimings>);
+
+impl Timings {
+    /// Reads the timings at `path`. Missing timings are treated as empty.
+    pub fn load(path: &Path) -> Result<Self> {
+        load_toml(path)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        save_toml(path, None, self)
+    }
+
+    /// Records the parse time and the time of every part that ran in `reports`. Parts that
+    /// didn't run keep their previous timings.
+    pub fn record(&mut self, reports: &[DayReport]) {
+        for report in reports {
+            let timings = self.0.entry(day_key(report.day)).or_default();
+            timings.parse_ns = Some(report.parse_duration.as_nanos() as u64);
+            for part in &report.parts {
+                let nanos = Some(part.duration.as_nanos() as u64);
+                match part.part {
+                    1 => timings.part1_ns = nanos,
+                    2 => timings.part2_ns = nanos,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// The last measured time of `part` of `day`.
+    pub fn get(&self, day: u8, part: u8) -> Option<Duration> {
+        let timings = self.0.get(&day_key(day))?;
+        let nanos = match part {
+            1 => timings.part1_ns,
+            2 => timings.part2_ns,
+            _ => None,
+        }?;
+        Some(Duration::from_nanos(nanos))
+    }
+}
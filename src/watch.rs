@@ -0,0 +1,210 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use color_eyre::{Result, eyre::bail};
+use tracing::{error, info, warn};
+
+/// How often watched files are checked for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification time and size of a file. Either changing counts as an edit.
+type Stamp = (SystemTime, u64);
+
+/// Detects changes to a set of files by polling their metadata. Directories are watched one level
+/// deep, so files added to or removed from them are noticed too.
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    extension: Option<&'static str>,
+    stamps: BTreeMap<PathBuf, Stamp>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let stamps = snapshot(&paths, None);
+        Watcher {
+            paths,
+            extension: None,
+            stamps,
+        }
+    }
+
+    /// Only watches files with the given extension, e.g. `rs`.
+    pub fn with_extension(mut self, extension: &'static str) -> Self {
+        self.extension = Some(extension);
+        self.stamps = snapshot(&self.paths, self.extension);
+        self
+    }
+
+    /// Files that changed, appeared or disappeared since the last call.
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let stamps = snapshot(&self.paths, self.extension);
+        let mut changed: Vec<PathBuf> = stamps
+            .iter()
+            .filter(|(path, stamp)| self.stamps.get(*path) != Some(stamp))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            self.stamps
+                .keys()
+                .filter(|path| !stamps.contains_key(*path))
+                .cloned(),
+        );
+        self.stamps = stamps;
+        changed
+    }
+}
+
+fn stamp(path: &Path) -> Option<Stamp> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+fn snapshot(paths: &[PathBuf], extension: Option<&str>) -> BTreeMap<PathBuf, Stamp> {
+    let mut stamps = BTreeMap::new();
+    for path in paths {
+        let files = match std::fs::read_dir(path) {
+            Ok(entries) => entries
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|path| path.is_file())
+                .collect(),
+            Err(_) => vec![path.clone()],
+        };
+        for file in files {
+            if extension.is_some_and(|ext| file.extension().is_none_or(|e| e != ext)) {
+                continue;
+            }
+            if let Some(stamp) = stamp(&file) {
+                stamps.insert(file, stamp);
+            }
+        }
+    }
+    stamps
+}
+
+/// The crate features this binary was built with, so a rebuild can use the same ones.
+fn enabled_features() -> Vec<&'static str> {
+    [
+        ("embed-inputs", cfg!(feature = "embed-inputs")),
+        ("generate", cfg!(feature = "generate")),
+    ]
+    .into_iter()
+    .filter_map(|(feature, enabled)| enabled.then_some(feature))
+    .collect()
+}
+
+/// Rebuilds the binary with cargo, using the same profile and features, and replaces this process
+/// with the new build, keeping the same arguments. Returns if the build fails, so watching can
+/// carry on with the old build.
+fn rebuild_and_exec(exe: &Path) -> Result<()> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut build = Command::new(cargo);
+    build
+        .args(["build", "--bin", env!("CARGO_BIN_NAME")])
+        .current_dir(env!("CARGO_MANIFEST_DIR"));
+    if !cfg!(debug_assertions) {
+        build.arg("--release");
+    }
+    let features = enabled_features();
+    if !features.is_empty() {
+        build.args(["--features", &features.join(",")]);
+    }
+    info!("Rebuilding");
+    if !build.status()?.success() {
+        bail!("Build failed");
+    }
+
+    let mut command = Command::new(exe);
+    command.args(std::env::args_os().skip(1));
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        Err(command.exec().into())
+    }
+    #[cfg(not(unix))]
+    {
+        std::process::exit(command.status()?.code().unwrap_or(1));
+    }
+}
+
+/// Calls `run` now and again whenever one of `inputs` changes. With `sources`, changes to the `.rs`
+/// files in them rebuild the binary and restart it instead. Errors from `run` are logged, not
+/// returned, so a broken input doesn't end the session.
+pub fn watch(
+    inputs: Vec<PathBuf>,
+    sources: Option<Vec<PathBuf>>,
+    mut run: impl FnMut() -> Result<()>,
+) -> Result<()> {
+    // Once the binary is rebuilt, the running one's path reads as deleted, so look it up first
+    let exe = std::env::current_exe()?;
+    let mut inputs = Watcher::new(inputs);
+    let mut sources = sources.map(|sources| Watcher::new(sources).with_extension("rs"));
+    let mut run_logged = || {
+        if let Err(err) = run() {
+            error!("{:?}", err);
+        }
+        info!("Watching for changes");
+    };
+    run_logged();
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        if let Some(sources) = &mut sources
+            && !sources.changed().is_empty()
+            && let Err(err) = rebuild_and_exec(&exe)
+        {
+            warn!("{}", err);
+            continue;
+        }
+        let changed = inputs.changed();
+        if !changed.is_empty() {
+            for path in &changed {
+                info!("Changed: {}", path.display());
+            }
+            run_logged();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use test_log::test;
+
+    #[test]
+    fn test_watcher() {
+        let dir = temp_dir("watch");
+        let file = dir.join("test1.txt");
+        std::fs::write(&file, "1").unwrap();
+        let mut watcher = Watcher::new(vec![dir.clone(), dir.join("examples.toml")]);
+        assert!(watcher.changed().is_empty());
+
+        std::fs::write(&file, "12").unwrap();
+        assert_eq!(watcher.changed(), vec![file.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        std::fs::write(dir.join("examples.toml"), "").unwrap();
+        assert_eq!(watcher.changed(), vec![dir.join("examples.toml")]);
+
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(watcher.changed(), vec![file]);
+    }
+
+    #[test]
+    fn test_watcher_extension() {
+        let dir = temp_dir("watch-extension");
+        let source = dir.join("mod.rs");
+        std::fs::write(&source, "").unwrap();
+        let mut watcher = Watcher::new(vec![dir.clone()]).with_extension("rs");
+
+        std::fs::write(dir.join("test1.txt"), "1").unwrap();
+        assert!(watcher.changed().is_empty());
+
+        std::fs::write(&source, "pub struct Day;").unwrap();
+        assert_eq!(watcher.changed(), vec![source]);
+    }
+}
@@ -0,0 +1,16 @@
+//! Runs every day's examples from its `examples.toml`.
+
+use test_log::test;
+
+macro_rules! example_tests {
+    ($($day_num:literal => $day_mod:ident),* $(,)?) => {
+        $(
+            #[test]
+            fn $day_mod() {
+                aoc::examples::check::<aoc::$day_mod::Day>($day_num).unwrap();
+            }
+        )*
+    };
+}
+
+aoc::all_days!(example_tests);
@@ -0,0 +1,54 @@
+//! Malformed inputs must make every day fail with an error rather than panic. The fuzz targets in
+//! `fuzz/` search for new ones; these are the ones that used to panic.
+
+use aoc::Solution;
+use test_log::test;
+
+const MALFORMED: &[&str] = &[
+    "",
+    "\n\n",
+    "é42",
+    "L",
+    "R-2147483648",
+    "1-2-3",
+    "0-9223372036854775807",
+    "9/",
+    "5\n\n18446744073709551615-1",
+    "1\n+",
+    "1 2 3\n+",
+    "1\n2 3\n+ +",
+    "99999999999\n99999999999\n99999999999\n*",
+    "S\n^",
+    ".S\n.^",
+    "\u{0}@\n@@@@@@@@@@@@@@@@@@@@",
+];
+
+macro_rules! malformed_tests {
+    ($($day_num:literal => $day_mod:ident),* $(,)?) => {
+        $(
+            #[test]
+            fn $day_mod() {
+                for input in MALFORMED {
+                    let _ = aoc::$day_mod::Day::validate(input);
+                    let _ = aoc::$day_mod::Day::solve_part1(input);
+                    let _ = aoc::$day_mod::Day::solve_part2(input);
+                }
+            }
+        )*
+    };
+}
+
+aoc::all_days!(malformed_tests);
+
+#[test]
+fn test_errors() {
+    assert!(aoc::day01::Day::solve_part1("é42").is_err());
+    assert!(aoc::day02::Day::solve_part2("1-9223372036854775807").is_err());
+    assert!(aoc::day03::Day::solve_part1("98x7").is_err());
+    assert!(aoc::day03::Day::solve_part2("987").is_err());
+    assert!(aoc::day05::Day::solve_part2("9-1\n\n5").is_err());
+    assert!(aoc::day06::Day::solve_part1("1\n2 3\n+ +").is_err());
+    let tall_column = format!("{}+", "9\n".repeat(25));
+    assert!(aoc::day06::Day::solve_part2(&tall_column).is_err());
+    assert!(aoc::day07::Day::solve_part1(".S\n.^").is_err());
+}